@@ -0,0 +1,46 @@
+//Times mapping an activation over a Matrix into a new one against applying it in place, and a
+//forward and backward pass through Dense layers, which apply their activations in place
+//
+//Run with `cargo bench --bench forward`
+use std::time::Instant;
+
+use triton_grow::network::{network::Network, matrix::Matrix, activations::Activations, layer::layers::LayerTypes};
+
+const SIZE: usize = 256;
+const RUNS: u32 = 100;
+
+fn main() {
+    let sigmoid = Activations::SIGMOID.get_function().function;
+    let mut matrix = Matrix::new_random(SIZE, SIZE);
+
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(matrix.map(&*sigmoid));
+    }
+    println!("map:           {:?} per run", start.elapsed() / RUNS);
+
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(&mut matrix).apply_inplace(&*sigmoid);
+    }
+    println!("apply_inplace: {:?} per run", start.elapsed() / RUNS);
+
+    let inputs: Vec<Vec<f32>> = (0..1000).map(|i| (0..64).map(|j| ((i * 64 + j) % 97) as f32 / 97.0).collect()).collect();
+    let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| vec![input[0]]).collect();
+
+    let mut new_net = Network::new(64);
+    new_net.add_layer(LayerTypes::DENSE(128, Activations::SIGMOID, 0.01));
+    new_net.add_layer(LayerTypes::DENSE(128, Activations::SIGMOID, 0.01));
+    new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.01));
+    new_net.compile();
+
+    let start = Instant::now();
+    for input in inputs.iter() {
+        std::hint::black_box(new_net.predict(input.clone()).unwrap());
+    }
+    println!("forward pass:  {:?} per sample", start.elapsed() / inputs.len() as u32);
+
+    let start = Instant::now();
+    new_net.fit(&inputs, &outputs, 1).unwrap();
+    println!("training step: {:?} per sample", start.elapsed() / inputs.len() as u32);
+}
@@ -0,0 +1,53 @@
+//Times Matrix multiplication against the plain triple loop the default build uses and, with the
+//ndarray feature, against ndarray's dot on arrays that are already converted. The gap between
+//Matrix::matmul and the bare dot is what converting to and from ndarray costs every call
+//
+//Run with `cargo bench --bench matmul` and `cargo bench --bench matmul --features ndarray`
+use std::time::{Duration, Instant};
+
+use triton_grow::network::matrix::Matrix;
+
+//(rows, inner, columns, runs), a square product and the weights times input of a Dense layer
+const SHAPES: [(usize, usize, usize, u32); 2] = [(256, 256, 256, 10), (256, 256, 1, 1000)];
+
+//The same triple loop Matrix::matmul runs without the ndarray feature
+fn naive(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut res = Matrix::new_empty(a.rows, b.columns);
+    for i in 0..a.rows {
+        for j in 0..b.columns {
+            let mut sum = 0.0;
+            for k in 0..a.columns {
+                sum += a.data[i][k] * b.data[k][j];
+            }
+            res.data[i][j] = sum;
+        }
+    }
+    res
+}
+
+fn time(runs: u32, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        f();
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    let backend = if cfg!(feature = "ndarray") { "ndarray" } else { "naive" };
+    for (rows, inner, columns, runs) in SHAPES {
+        let a = Matrix::new_random(rows, inner);
+        let b = Matrix::new_random(inner, columns);
+        let shape = format!("{}x{} * {}x{}", rows, inner, inner, columns);
+
+        println!("{:<18} {:<26} {:?} per run", shape, "naive loop", time(runs, || { std::hint::black_box(naive(&a, &b)); }));
+        println!("{:<18} {:<26} {:?} per run", shape, format!("Matrix::matmul ({})", backend), time(runs, || { std::hint::black_box(a.matmul(&b).unwrap()); }));
+
+        #[cfg(feature = "ndarray")]
+        {
+            let a = ndarray::Array2::from_shape_vec((rows, inner), a.data.concat()).unwrap();
+            let b = ndarray::Array2::from_shape_vec((inner, columns), b.data.concat()).unwrap();
+            println!("{:<18} {:<26} {:?} per run", shape, "ndarray dot", time(runs, || { std::hint::black_box(a.dot(&b)); }));
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//Compares running 10k samples through a network one at a time against predict_parallel
+//
+//Run with `cargo bench --bench predict`
+use std::time::Instant;
+
+use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+
+fn main() {
+    let inputs: Vec<Vec<f32>> = (0..10_000)
+        .map(|i| (0..16).map(|j| ((i * 16 + j) % 97) as f32 / 97.0).collect())
+        .collect();
+
+    let mut new_net = Network::new(16);
+    new_net.add_layer(LayerTypes::DENSE(64, Activations::RELU, 0.1));
+    new_net.add_layer(LayerTypes::DENSE(64, Activations::RELU, 0.1));
+    new_net.add_layer(LayerTypes::DENSE(4, Activations::SOFTMAX, 0.1));
+    new_net.compile();
+
+    let start = Instant::now();
+    let serial: Vec<Vec<f32>> = inputs.iter().map(|input| new_net.predict(input.clone()).unwrap()).collect();
+    let serial_time = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = new_net.predict_parallel(&inputs).unwrap();
+    let parallel_time = start.elapsed();
+
+    assert_eq!(serial, parallel);
+    println!("serial:   {:?}", serial_time);
+    println!("parallel: {:?} across {} threads", parallel_time, rayon::current_num_threads());
+}
@@ -0,0 +1,69 @@
+//Counts the allocations transpose makes against transpose_in_place, and those of a training step
+//through square Dense layers, which take a single transposed copy of their weights and inputs during
+//back propegation
+//
+//Run with `cargo bench --bench transpose`
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use triton_grow::network::{network::Network, matrix::Matrix, activations::Activations, layer::layers::LayerTypes};
+
+const SIZE: usize = 64;
+const RUNS: usize = 1000;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+//The allocations and time `f` takes
+fn measure(f: impl FnOnce()) -> (usize, std::time::Duration) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    f();
+    (ALLOCATIONS.load(Ordering::Relaxed) - before, start.elapsed())
+}
+
+fn main() {
+    let mut matrix = Matrix::new_random(SIZE, SIZE);
+
+    let (allocations, time) = measure(|| {
+        for _ in 0..RUNS {
+            matrix = std::hint::black_box(matrix.transpose());
+        }
+    });
+    println!("transpose:          {} allocations, {:?} per run", allocations / RUNS, time / RUNS as u32);
+
+    let (allocations, time) = measure(|| {
+        for _ in 0..RUNS {
+            std::hint::black_box(&mut matrix).transpose_in_place();
+        }
+    });
+    println!("transpose_in_place: {} allocations, {:?} per run", allocations / RUNS, time / RUNS as u32);
+
+    let inputs: Vec<Vec<f32>> = (0..100).map(|i| (0..SIZE).map(|j| ((i * SIZE + j) % 97) as f32 / 97.0).collect()).collect();
+    let outputs: Vec<Vec<f32>> = inputs.clone();
+
+    let mut new_net = Network::new(SIZE);
+    new_net.add_layer(LayerTypes::DENSE(SIZE, Activations::RELU, 0.01));
+    new_net.add_layer(LayerTypes::DENSE(SIZE, Activations::SIGMOID, 0.01));
+    new_net.compile();
+
+    let (allocations, time) = measure(|| { new_net.fit(&inputs, &outputs, 1).unwrap(); });
+    println!("training step:      {} allocations, {:?} per sample", allocations / inputs.len(), time / inputs.len() as u32);
+}
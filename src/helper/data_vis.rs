@@ -16,15 +16,15 @@ impl Network {
         chart.configure_mesh().draw()?;
         
         chart.configure_series_labels()
-        .border_style(&BLACK)
+        .border_style(BLACK)
         .label_font("Arial")
         .draw()?;
 
         chart.draw_series(LineSeries::new(data.clone(), &BLACK))?;
         chart.draw_series(PointSeries::of_element(data, 2, &BLACK, &|c, s, st| {
-            return EmptyElement::at(c)    // We want to construct a composed element on-the-fly
+            EmptyElement::at(c)    // We want to construct a composed element on-the-fly
             + Circle::new((0,0),s,st.filled()) // At this point, the new pixel coordinate is established
-            + Text::new(format!("({}, {:.2})", c.0 as i32, c.1), (10, 0), ("sans-serif", 10).into_font());
+            + Text::new(format!("({}, {:.2})", c.0 as i32, c.1), (10, 0), ("sans-serif", 10).into_font())
         }))?;
 
         root.present()?;
@@ -40,26 +40,25 @@ impl Network {
             .margin(5)
             .x_label_area_size(30)
             .y_label_area_size(30)
-            .build_cartesian_2d(-0.1f32..data.len() as f32, -0.1f32..data.iter().max_by(|l, j| l.total_cmp(&j)).unwrap() + 1.0)?;
+            .build_cartesian_2d(-0.1f32..data.len() as f32, -0.1f32..data.iter().max_by(|l, j| l.total_cmp(j)).unwrap() + 1.0)?;
 
         chart.configure_mesh().draw()?;
         
         chart.configure_series_labels()
-        .border_style(&BLACK)
+        .border_style(BLACK)
         .label_font("Arial")
         .draw()?;
 
-        let mut data_ord: Vec<(f32, f32)> = vec![];
-
-        for i in 0..data.len(){
-            data_ord.push((i as f32, data[i]));
-        }
+        let data_ord: Vec<(f32, f32)> = data.iter()
+            .enumerate()
+            .map(|(i, loss)| (i as f32, *loss))
+            .collect();
 
         chart.draw_series(LineSeries::new(data_ord.clone(), &BLACK))?;
         chart.draw_series(PointSeries::of_element(data_ord, 2, &BLACK, &|c, s, st| {
-            return EmptyElement::at(c)    // We want to construct a composed element on-the-fly
+            EmptyElement::at(c)    // We want to construct a composed element on-the-fly
             + Circle::new((0,0),s,st.filled()) // At this point, the new pixel coordinate is established
-            + Text::new(format!("({}, {:.2})", c.0 as i32, c.1), (10, 0), ("sans-serif", 10).into_font());
+            + Text::new(format!("({}, {:.2})", c.0 as i32, c.1), (10, 0), ("sans-serif", 10).into_font())
         }))?;
 
         root.present()?;
@@ -67,3 +66,31 @@ impl Network {
         Ok(())
     }
 }
+
+///Formats a confusion matrix as a table, each row is an actual class and each column the class it
+///was predicted as
+///
+///```
+///use triton_grow::helper::data_vis::pretty_print_confusion;
+///
+///let table = pretty_print_confusion(&vec![vec![5, 1], vec![0, 4]]);
+///assert_eq!(table, concat!(
+///    "actual\\predicted     0     1\n",
+///    "               0     5     1\n",
+///    "               1     0     4\n"));
+///```
+pub fn pretty_print_confusion(confusion: &[Vec<usize>]) -> String {
+    let mut res = String::from("actual\\predicted");
+    for predicted in 0..confusion.len() {
+        res += &format!(" {:>5}", predicted);
+    }
+    res += "\n";
+    for (actual, row) in confusion.iter().enumerate() {
+        res += &format!("{:>16}", actual);
+        for count in row {
+            res += &format!(" {:>5}", count);
+        }
+        res += "\n";
+    }
+    res
+}
@@ -1,4 +1,4 @@
-use triton_grow::network::{network::Network, activations::Activations, layer::{layers::LayerTypes, conv::Convolutional}, input::Input, matrix::Matrix};
+use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, initializer::Initializer};
 
 fn main() {
     let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
@@ -6,17 +6,17 @@ fn main() {
 
     let mut new_net = Network::new(2);
 
-    new_net.add_layer(LayerTypes::DENSE(2, Activations::RELU, 0.1));
-    new_net.add_layer(LayerTypes::DENSE(3, Activations::RELU, 0.1));
+    new_net.add_layer(LayerTypes::DENSEINIT(3, Activations::RELU, 0.1, Initializer::He));
     new_net.add_layer(LayerTypes::DENSE(1, Activations::RELU, 0.1));
 
     new_net.compile();
+    new_net.set_verbose(true);
 
-    new_net.fit(&inputs, &outputs, 100);
+    new_net.fit(&inputs, &outputs, 5000).unwrap();
 
-    //let mut new_net = Network::load("best_network.json");
-    println!("1 and 0: {:?}", new_net.predict(vec![1.0,0.0])[0]);
-    println!("0 and 1: {:?}", new_net.predict(vec![0.0,1.0])[0]);
-    println!("1 and 1: {:?}", new_net.predict(vec![1.0,1.0])[0]);
-    println!("0 and 0: {:?}", new_net.predict(vec![0.0,0.0])[0]);
+    //let mut new_net = Network::load("best_network.json").unwrap();
+    println!("1 and 0: {:?}", new_net.predict(vec![1.0,0.0]).unwrap()[0]);
+    println!("0 and 1: {:?}", new_net.predict(vec![0.0,1.0]).unwrap()[0]);
+    println!("1 and 1: {:?}", new_net.predict(vec![1.0,1.0]).unwrap()[0]);
+    println!("0 and 0: {:?}", new_net.predict(vec![0.0,0.0]).unwrap()[0]);
 }
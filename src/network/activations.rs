@@ -1,68 +1,349 @@
-use std::f32::consts::E;
-use serde::{Deserialize, Serialize};
+use std::{f32::consts::E, sync::{Arc, RwLock}};
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Clone)]
-pub struct Activation<'a>{
-    pub function: &'a dyn Fn(f32) -> f32,
-    pub derivative: &'a dyn Fn(f32) -> f32
+use super::matrix::Matrix;
+
+///The negative slope commonly used for LEAKYRELU
+pub const DEFAULT_LEAKY_SLOPE: f32 = 0.01;
+
+pub struct Activation{
+    pub function: Box<dyn Fn(f32) -> f32>,
+    pub derivative: Box<dyn Fn(f32) -> f32>
 }
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+///The activation functions available to a layer, saved alongside the layer itself
+///
+///```
+///use triton_grow::network::activations::Activations;
+///
+///let saved = serde_json::to_string(&Activations::LEAKYRELU(0.2)).unwrap();
+///let loaded: Activations = serde_json::from_str(&saved).unwrap();
+///assert_eq!((loaded.get_function().function)(-1.0), -0.2);
+///
+///let saved = serde_json::to_string(&Activations::GELU).unwrap();
+///assert_eq!(saved, "\"GELU\"");
+///
+///let saved = serde_json::to_string(&Activations::ELU(0.5)).unwrap();
+///let loaded: Activations = serde_json::from_str(&saved).unwrap();
+///let elu = loaded.get_function();
+///assert_eq!((elu.function)(2.0), 2.0);
+///assert!(((elu.function)(-1.0) - 0.5 * ((-1.0f32).exp() - 1.0)).abs() < 1e-6);
+///assert!(((elu.derivative)(-1.0) - 0.5 * (-1.0f32).exp()).abs() < 1e-6);
+///```
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Activations{
     SIGMOID,
     TANH,
     RELU,
-    LEAKYRELU
+    //LEAKYRELU: Negative slope
+    LEAKYRELU(f32),
+    SOFTMAX,
+    GELU,
+    //ELU: Alpha, the value negative inputs saturate towards
+    ELU(f32),
+    SWISH,
+    MISH,
+    //CUSTOM: Name the activation was registered under, see register_activation
+    CUSTOM(#[serde(deserialize_with = "registered_name")] ActivationName)
 }
 impl Activations{
     pub fn get_function(&self) -> Activation{
-        return match self{
-            Activations::SIGMOID => SIGMOID,
-            Activations::TANH => TANH,
-            Activations::RELU => RELU,
-            Activations::LEAKYRELU => LEAKY_RELU
-        };
+        match self{
+            Activations::SIGMOID => sigmoid(),
+            Activations::TANH => tanh(),
+            Activations::RELU => relu(),
+            Activations::LEAKYRELU(slope) => leaky_relu(*slope),
+            Activations::SOFTMAX => softmax(),
+            Activations::GELU => gelu(),
+            Activations::ELU(alpha) => elu(*alpha),
+            Activations::SWISH => swish(),
+            Activations::MISH => mish(),
+            Activations::CUSTOM(name) => custom(name)
+        }
+    }
+    ///Applies the activation to a matrix of pre-activation values where every column is one sample
+    ///
+    ///Element-wise activations are simply mapped over the matrix, while SOFTMAX normalizes each
+    ///column so that it sums to 1
+    pub fn apply(&self, mut data: Matrix) -> Matrix {
+        match self {
+            Activations::SOFTMAX => {
+                let mut res = Matrix::new_empty(data.rows, data.columns);
+                for col in 0..data.columns {
+                    let max = (0..data.rows).map(|row| data.data[row][col]).fold(f32::NEG_INFINITY, f32::max);
+                    let mut sum = 0.0;
+                    for row in 0..data.rows {
+                        res.data[row][col] = (data.data[row][col] - max).exp();
+                        sum += res.data[row][col];
+                    }
+                    for row in 0..data.rows {
+                        res.data[row][col] /= sum;
+                    }
+                }
+                res
+            },
+            _ => {
+                data.apply_inplace(&*self.get_function().function);
+                data
+            }
+        }
     }
 }
 
+///An element-wise activation defined outside of the crate, see register_activation
+pub trait ActivationFn: Send + Sync {
+    fn function(&self, x: f32) -> f32;
+    fn derivative(&self, x: f32) -> f32;
+}
 
-const SIGMOID: Activation = Activation {
-    function: &|x| {
-        let res = 1.0 / (1.0 + E.powf(-x));
-        return res;
-    },
-    derivative: &|x| x * (1.0 - x)
-};
-
-const TANH: Activation = Activation {
-    function: &|x| {
-        let res = f32::tanh(x);
-        return res;
-    },
-    derivative: &|x| 1.0 - f32::tanh(x).powf(2.0)
-};
-
-const RELU: Activation = Activation {
-    function: &|x| x.max(0.0),
-    derivative: &|x| {
-        if x.max(0.0) == x {
-            return 1.0;
-        }
-        return 0.0;
+///The name a custom activation is registered and saved under
+//Spelled out as &'static str inside Activations, serde's derive would make deserializing borrow
+//from the input for 'static
+pub type ActivationName = &'static str;
+
+static REGISTRY: RwLock<Vec<(&'static str, Arc<dyn ActivationFn>)>> = RwLock::new(vec![]);
+
+///Registers `activation` under `name` so layers can use it as Activations::CUSTOM(name),
+///registering a name again replaces the activation behind it
+///
+///Only the name is saved with a network, the activation itself can't be. Loading a network (or
+///deserializing Activations) that uses a custom activation fails unless its name has been
+///registered beforehand, and it's up to the caller to register the same activation under that name
+///every time the program runs
+///
+///# Example
+///
+///```
+///use triton_grow::network::activations::{Activations, ActivationFn, register_activation};
+///
+///struct Softsign;
+///impl ActivationFn for Softsign {
+///    fn function(&self, x: f32) -> f32 {
+///        x / (1.0 + x.abs())
+///    }
+///    fn derivative(&self, x: f32) -> f32 {
+///        1.0 / (1.0 + x.abs()).powi(2)
+///    }
+///}
+///
+///assert!(serde_json::from_str::<Activations>("{\"CUSTOM\":\"softsign\"}").is_err());
+///
+///register_activation("softsign", Softsign);
+///let softsign = Activations::CUSTOM("softsign").get_function();
+///assert_eq!((softsign.function)(1.0), 0.5);
+///assert_eq!((softsign.derivative)(1.0), 0.25);
+///
+///let saved = serde_json::to_string(&Activations::CUSTOM("softsign")).unwrap();
+///assert_eq!(saved, "{\"CUSTOM\":\"softsign\"}");
+///let loaded: Activations = serde_json::from_str(&saved).unwrap();
+///assert_eq!((loaded.get_function().function)(-3.0), -0.75);
+///```
+pub fn register_activation(name: &'static str, activation: impl ActivationFn + 'static) {
+    let mut registry = REGISTRY.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    registry.retain(|(registered, _)| *registered != name);
+    registry.push((name, Arc::new(activation)));
+}
+
+fn registered(name: &str) -> Option<(&'static str, Arc<dyn ActivationFn>)> {
+    let registry = REGISTRY.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    registry.iter().find(|(registered, _)| *registered == name).cloned()
+}
+
+//Names are only ever deserialized into one that has been registered, which is what gives them a
+//'static lifetime
+fn registered_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static str, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    registered(&name).map(|(registered, _)| registered).ok_or_else(|| serde::de::Error::custom(format!("custom activation {} has not been registered", name)))
+}
+
+fn custom(name: &str) -> Activation {
+    let (_, activation) = registered(name).unwrap_or_else(|| panic!("Error attempting to use custom activation {}, it has not been registered", name));
+    let derivative = activation.clone();
+    Activation {
+        function: Box::new(move |x| activation.function(x)),
+        derivative: Box::new(move |x| derivative.derivative(x))
     }
-};
+}
 
-const LEAKY_RELU: Activation = Activation{
-    function: &|x| {
-        if x.max(0.0) == x{
-            return x;
-        }
-        return 0.001 * x;
-    },
-    derivative: &|x| {
-        if x.max(0.0) == x{
-            return 1.0
-        }
-        return 0.001;
+///Only ever raises e to a negative power, so large inputs of either sign can't overflow
+///
+///```
+///use triton_grow::network::activations::Activations;
+///
+///let sigmoid = Activations::SIGMOID.get_function();
+///for x in [-100.0, 100.0] {
+///    let res = (sigmoid.function)(x);
+///    assert!(res.is_finite() && (0.0..=1.0).contains(&res));
+///}
+///assert_eq!((sigmoid.function)(0.0), 0.5);
+///```
+fn sigmoid() -> Activation {
+    Activation {
+        function: Box::new(stable_sigmoid),
+        derivative: Box::new(|x| {
+            let sigmoid = stable_sigmoid(x);
+            sigmoid * (1.0 - sigmoid)
+        })
     }
-};
+}
+
+//Large negative inputs would overflow e^-x, so those are computed as e^x / (1 + e^x) instead
+fn stable_sigmoid(x: f32) -> f32 {
+    if x >= 0.0 {
+        1.0 / (1.0 + E.powf(-x))
+    } else {
+        let exp = E.powf(x);
+        exp / (1.0 + exp)
+    }
+}
+
+fn tanh() -> Activation {
+    Activation {
+        function: Box::new(f32::tanh),
+        derivative: Box::new(|x| 1.0 - f32::tanh(x).powf(2.0))
+    }
+}
+
+fn relu() -> Activation {
+    Activation {
+        function: Box::new(|x| x.max(0.0)),
+        derivative: Box::new(|x| {
+            if x.max(0.0) == x {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+}
+
+fn leaky_relu(slope: f32) -> Activation {
+    Activation {
+        function: Box::new(move |x| {
+            if x.max(0.0) == x{
+                x
+            } else {
+                slope * x
+            }
+        }),
+        derivative: Box::new(move |x| {
+            if x.max(0.0) == x{
+                1.0
+            } else {
+                slope
+            }
+        })
+    }
+}
+
+fn elu(alpha: f32) -> Activation {
+    Activation {
+        function: Box::new(move |x| {
+            if x >= 0.0 {
+                x
+            } else {
+                alpha * (x.exp() - 1.0)
+            }
+        }),
+        derivative: Box::new(move |x| {
+            if x >= 0.0 {
+                1.0
+            } else {
+                alpha * x.exp()
+            }
+        })
+    }
+}
+
+///Swish (SiLU), x * sigmoid(x)
+///
+///```
+///use triton_grow::network::activations::Activations;
+///
+///let swish = Activations::SWISH.get_function();
+///assert_eq!((swish.function)(0.0), 0.0);
+///assert!(((swish.function)(1.0) - 0.7311).abs() < 1e-4);
+///assert_eq!((swish.function)(-200.0), 0.0);
+///assert!(((swish.derivative)(0.0) - 0.5).abs() < 1e-6);
+///```
+fn swish() -> Activation {
+    Activation {
+        function: Box::new(|x| x * stable_sigmoid(x)),
+        derivative: Box::new(|x| {
+            let sigmoid = stable_sigmoid(x);
+            sigmoid + x * sigmoid * (1.0 - sigmoid)
+        })
+    }
+}
+
+///Mish, x * tanh(softplus(x))
+///
+///```
+///use triton_grow::network::activations::Activations;
+///
+///let mish = Activations::MISH.get_function();
+///assert_eq!((mish.function)(0.0), 0.0);
+///assert!(((mish.function)(1.0) - 0.8651).abs() < 1e-4);
+///assert_eq!((mish.function)(200.0), 200.0);
+///assert!((mish.function)(-200.0).abs() < 1e-6);
+///assert!(((mish.derivative)(0.0) - 0.6).abs() < 1e-4);
+///assert!((mish.derivative)(200.0).is_finite() && (mish.derivative)(-200.0).is_finite());
+///```
+fn mish() -> Activation {
+    Activation {
+        function: Box::new(|x| x * softplus(x).tanh()),
+        derivative: Box::new(|x| {
+            let tanh = softplus(x).tanh();
+            tanh + x * (1.0 - tanh.powi(2)) * stable_sigmoid(x)
+        })
+    }
+}
+
+//Past the threshold ln(1 + e^x) equals x (or e^x for negative x) to within f32 precision, and
+//e^x would otherwise overflow
+const SOFTPLUS_THRESHOLD: f32 = 20.0;
+
+fn softplus(x: f32) -> f32 {
+    if x > SOFTPLUS_THRESHOLD {
+        x
+    } else if x < -SOFTPLUS_THRESHOLD {
+        x.exp()
+    } else {
+        x.exp().ln_1p()
+    }
+}
+
+//sqrt(2 / pi), used by the tanh approximation of GELU
+const GELU_SCALE: f32 = 0.797_884_6;
+const GELU_CUBIC: f32 = 0.044715;
+
+///GELU using the tanh approximation 0.5x(1 + tanh(sqrt(2/pi)(x + 0.044715x^3)))
+///
+///```
+///use triton_grow::network::activations::Activations;
+///
+///let gelu = Activations::GELU.get_function();
+///assert_eq!((gelu.function)(0.0), 0.0);
+///assert!(((gelu.function)(1.0) - 0.8412).abs() < 1e-3);
+///assert!(((gelu.derivative)(0.0) - 0.5).abs() < 1e-6);
+///```
+fn gelu() -> Activation {
+    Activation {
+        function: Box::new(|x| 0.5 * x * (1.0 + (GELU_SCALE * (x + GELU_CUBIC * x.powi(3))).tanh())),
+        derivative: Box::new(|x| {
+            let inner = (GELU_SCALE * (x + GELU_CUBIC * x.powi(3))).tanh();
+            let sech_squared = 1.0 - inner.powi(2);
+            0.5 * (1.0 + inner) + 0.5 * x * sech_squared * GELU_SCALE * (1.0 + 3.0 * GELU_CUBIC * x.powi(2))
+        })
+    }
+}
+
+//Softmax can't be expressed element-wise, see Activations::apply for the normalization step. The
+//derivative is 1 as softmax is expected to be paired with a cross-entropy loss, where the gradient
+//at the output simplifies to (target - predicted)
+fn softmax() -> Activation {
+    Activation {
+        function: Box::new(|x| x.exp()),
+        derivative: Box::new(|_| 1.0)
+    }
+}
@@ -0,0 +1,183 @@
+use serde_json::{Map, Number, Value};
+
+use super::error::NetworkError;
+
+//Compact binary encoding of a serialized network, every value starts with one of these tags.
+//Floats that survive being narrowed to f32 (every weight) are stored in 4 bytes and integers and
+//lengths as LEB128 varints, so a saved network is a fraction of the size of its JSON
+//
+//This isn't bincode because the layers are typetag trait objects, which deserialize through
+//deserialize_any and so need a self describing format. bincode isn't, and it isn't a dependency
+//of the crate either, so the network goes through serde_json's Value and every value carries a
+//tag saying what it is
+const MAGIC: &[u8; 4] = b"TRTN";
+const VERSION: u8 = 1;
+
+const NULL: u8 = 0;
+const FALSE: u8 = 1;
+const TRUE: u8 = 2;
+const UINT: u8 = 3;
+const INT: u8 = 4;
+const FLOAT32: u8 = 5;
+const FLOAT64: u8 = 6;
+const STRING: u8 = 7;
+const ARRAY: u8 = 8;
+const OBJECT: u8 = 9;
+
+///Encodes a serialized value into the binary format
+pub(crate) fn encode(value: &Value) -> Vec<u8> {
+    let mut res = MAGIC.to_vec();
+    res.push(VERSION);
+    encode_value(value, &mut res);
+    res
+}
+
+///Decodes a value previously written by encode
+pub(crate) fn decode(bytes: &[u8]) -> Result<Value, NetworkError> {
+    if bytes.len() < MAGIC.len() + 1 || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(NetworkError::Serialization(String::from("not a binary triton network")));
+    }
+    if bytes[MAGIC.len()] != VERSION {
+        return Err(NetworkError::Serialization(format!("unsupported binary version {}", bytes[MAGIC.len()])));
+    }
+    let mut reader = Reader { bytes, pos: MAGIC.len() + 1 };
+    let value = reader.value()?;
+    if reader.pos != bytes.len() {
+        return Err(NetworkError::Serialization(String::from("trailing bytes after network")));
+    }
+    Ok(value)
+}
+
+fn encode_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.push(NULL),
+        Value::Bool(false) => out.push(FALSE),
+        Value::Bool(true) => out.push(TRUE),
+        Value::Number(number) => {
+            if let Some(uint) = number.as_u64() {
+                out.push(UINT);
+                write_varint(uint, out);
+            } else if let Some(int) = number.as_i64() {
+                out.push(INT);
+                write_varint(((int << 1) ^ (int >> 63)) as u64, out);
+            } else {
+                let float = number.as_f64().unwrap_or(0.0);
+                if (float as f32) as f64 == float {
+                    out.push(FLOAT32);
+                    out.extend_from_slice(&(float as f32).to_le_bytes());
+                } else {
+                    out.push(FLOAT64);
+                    out.extend_from_slice(&float.to_le_bytes());
+                }
+            }
+        },
+        Value::String(string) => {
+            out.push(STRING);
+            write_str(string, out);
+        },
+        Value::Array(values) => {
+            out.push(ARRAY);
+            write_varint(values.len() as u64, out);
+            for value in values {
+                encode_value(value, out);
+            }
+        },
+        Value::Object(map) => {
+            out.push(OBJECT);
+            write_varint(map.len() as u64, out);
+            for (key, value) in map {
+                write_str(key, out);
+                encode_value(value, out);
+            }
+        }
+    }
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_str(string: &str, out: &mut Vec<u8>) {
+    write_varint(string.len() as u64, out);
+    out.extend_from_slice(string.as_bytes());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], NetworkError> {
+        if self.bytes.len() - self.pos < len {
+            return Err(NetworkError::Serialization(String::from("unexpected end of binary network")));
+        }
+        let res = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(res)
+    }
+    fn varint(&mut self) -> Result<u64, NetworkError> {
+        let mut res: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            res |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(res);
+            }
+        }
+        Err(NetworkError::Serialization(String::from("varint is too long")))
+    }
+    fn string(&mut self) -> Result<String, NetworkError> {
+        let len = self.varint()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|err| NetworkError::Serialization(err.to_string()))
+    }
+    fn value(&mut self) -> Result<Value, NetworkError> {
+        let tag = self.take(1)?[0];
+        Ok(match tag {
+            NULL => Value::Null,
+            FALSE => Value::Bool(false),
+            TRUE => Value::Bool(true),
+            UINT => Value::from(self.varint()?),
+            INT => {
+                let zigzag = self.varint()?;
+                Value::from((zigzag >> 1) as i64 ^ -((zigzag & 1) as i64))
+            },
+            FLOAT32 => {
+                let bytes: [u8; 4] = self.take(4)?.try_into().unwrap();
+                float_value(f32::from_le_bytes(bytes) as f64)
+            },
+            FLOAT64 => {
+                let bytes: [u8; 8] = self.take(8)?.try_into().unwrap();
+                float_value(f64::from_le_bytes(bytes))
+            },
+            STRING => Value::String(self.string()?),
+            ARRAY => {
+                let len = self.varint()? as usize;
+                let mut values = Vec::with_capacity(len.min(self.bytes.len()));
+                for _ in 0..len {
+                    values.push(self.value()?);
+                }
+                Value::Array(values)
+            },
+            OBJECT => {
+                let len = self.varint()? as usize;
+                let mut map = Map::new();
+                for _ in 0..len {
+                    let key = self.string()?;
+                    map.insert(key, self.value()?);
+                }
+                Value::Object(map)
+            },
+            _ => return Err(NetworkError::Serialization(format!("unknown value tag {}", tag))),
+        })
+    }
+}
+
+//JSON has no NaN or infinity, serde_json writes those as null so the binary format does the same
+fn float_value(float: f64) -> Value {
+    Number::from_f64(float).map_or(Value::Null, Value::Number)
+}
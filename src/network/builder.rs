@@ -0,0 +1,111 @@
+use super::activations::Activations;
+use super::initializer::Initializer;
+use super::error::NetworkError;
+use super::layer::layers::LayerTypes;
+use super::network::Network;
+
+///A fluent way of constructing a Network that validates every layer and compiles the network
+///when it's built
+///
+///# Example
+///
+///```
+///use triton_grow::network::{builder::NetworkBuilder, activations::Activations};
+///
+///let mut new_net = NetworkBuilder::new()
+///    .input(2)
+///    .dense(3, Activations::RELU, 0.01)
+///    .dropout(0.2)
+///    .dense(1, Activations::SIGMOID, 0.01)
+///    .build()
+///    .unwrap();
+///
+///assert_eq!(new_net.predict(vec![1.0, 0.0]).unwrap().len(), 1);
+///assert!(NetworkBuilder::new().input(2).build().is_err());
+///assert!(NetworkBuilder::new().input(2).dense(0, Activations::RELU, 0.01).build().is_err());
+///```
+#[derive(Default)]
+pub struct NetworkBuilder{
+    input_size: Option<usize>,
+    layers: Vec<LayerTypes>,
+}
+
+impl NetworkBuilder{
+    pub fn new() -> NetworkBuilder {
+        NetworkBuilder {
+            input_size: None,
+            layers: vec![],
+        }
+    }
+    ///Sets how many values each input to the network has
+    pub fn input(mut self, size: usize) -> NetworkBuilder {
+        self.input_size = Some(size);
+        self
+    }
+    ///Adds a Dense layer of `size` nodes
+    pub fn dense(mut self, size: usize, activation: Activations, learning_rate: f32) -> NetworkBuilder {
+        self.layers.push(LayerTypes::DENSE(size, activation, learning_rate));
+        self
+    }
+    ///Adds a Dense layer of `size` nodes whose weights are filled in by `initializer`
+    pub fn dense_init(mut self, size: usize, activation: Activations, learning_rate: f32, initializer: Initializer) -> NetworkBuilder {
+        self.layers.push(LayerTypes::DENSEINIT(size, activation, learning_rate, initializer));
+        self
+    }
+    ///Adds a Dropout layer that drops each of the previous layer's outputs with a probability of
+    ///`rate` while training
+    pub fn dropout(mut self, rate: f32) -> NetworkBuilder {
+        self.layers.push(LayerTypes::DROPOUT(rate));
+        self
+    }
+    ///Adds every layer of `layers` in order, validated along with the rest when the network is
+    ///built
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{builder::NetworkBuilder, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let hidden = vec![LayerTypes::DENSE(4, Activations::RELU, 0.01); 3];
+    ///let new_net = NetworkBuilder::new()
+    ///    .input(2)
+    ///    .add_layers(hidden)
+    ///    .dense(1, Activations::SIGMOID, 0.01)
+    ///    .build()
+    ///    .unwrap();
+    ///
+    ///assert_eq!(new_net.layer_sizes, vec![2, 4, 4, 4, 1]);
+    ///assert!(NetworkBuilder::new().input(2).add_layers(vec![LayerTypes::DROPOUT(1.5)]).dense(1, Activations::SIGMOID, 0.01).build().is_err());
+    ///```
+    pub fn add_layers(mut self, layers: Vec<LayerTypes>) -> NetworkBuilder {
+        self.layers.extend(layers);
+        self
+    }
+    ///Validates every layer and builds the compiled Network
+    ///
+    ///Fails if no input size was given, if there are no layers, if a layer is empty or has an
+    ///invalid dropout rate, or if the last layer can't act as an output layer
+    pub fn build(self) -> Result<Network, NetworkError> {
+        let input_size = match self.input_size {
+            Some(0) => return Err(NetworkError::InvalidLayer { index: 0, reason: String::from("input size must be greater than 0") }),
+            Some(size) => size,
+            None => return Err(NetworkError::MissingInputSize),
+        };
+        match self.layers.last() {
+            None => return Err(NetworkError::EmptyNetwork),
+            Some(LayerTypes::DENSE(_, _, _)) | Some(LayerTypes::DENSEINIT(_, _, _, _)) => {},
+            Some(_) => return Err(NetworkError::InvalidOutputLayer),
+        }
+
+        let mut network = Network::new(input_size);
+        for (index, layer) in self.layers.into_iter().enumerate() {
+            match layer {
+                LayerTypes::DENSE(0, _, _) | LayerTypes::DENSEINIT(0, _, _, _) => return Err(NetworkError::InvalidLayer { index, reason: String::from("dense layers need at least 1 node") }),
+                LayerTypes::DROPOUT(rate) if !(0.0..1.0).contains(&rate) => return Err(NetworkError::InvalidLayer { index, reason: String::from("dropout rate must be in [0, 1)") }),
+                _ => network.add_layer(layer),
+            }
+        }
+        network.compile();
+        Ok(network)
+    }
+}
@@ -0,0 +1,243 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::error::DataError;
+
+///The features and targets of a dataset, one Vec per sample in both
+pub type Dataset = (Vec<Vec<f32>>, Vec<Vec<f32>>);
+
+///A dataset split by train_test_split into its training inputs and targets followed by its test
+///inputs and targets
+pub type TrainTestSplit = (Vec<Vec<f32>>, Vec<Vec<f32>>, Vec<Vec<f32>>, Vec<Vec<f32>>);
+
+///How load_csv_with_options reads a dataset
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CsvOptions {
+    ///Skips the first row of the file, on by default
+    pub has_header: bool,
+    ///Rescales every feature column to [0, 1] by its minimum and maximum, off by default.
+    ///Columns that never change are set to 0, target columns are never touched
+    pub normalize: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { has_header: true, normalize: false }
+    }
+}
+
+///Loads a CSV dataset of numbers with a header row, splitting every row into its features and
+///its targets (the columns in `target_columns`, in that order). Both are ready to be handed to
+///fit as they are
+///
+///# Example
+///
+///```
+///use triton_grow::network::data::load_csv;
+///
+///let path = std::env::temp_dir().join("triton_load_csv_example.csv");
+///std::fs::write(&path, "a,b,label\n0,0,0\n1,0,1\n0,1,1\n1,1,0\n").unwrap();
+///
+///let (inputs, outputs) = load_csv(path.to_str().unwrap(), &[2]).unwrap();
+///assert_eq!(inputs, vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]);
+///assert_eq!(outputs, vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]]);
+///
+///assert!(load_csv(path.to_str().unwrap(), &[3]).is_err());
+///```
+pub fn load_csv(path: &str, target_columns: &[usize]) -> Result<Dataset, DataError> {
+    load_csv_with_options(path, target_columns, CsvOptions::default())
+}
+
+///Loads a CSV dataset exactly like load_csv, reading it according to `options`
+///
+///# Example
+///
+///```
+///use triton_grow::network::data::{load_csv_with_options, CsvOptions};
+///
+///let path = std::env::temp_dir().join("triton_load_csv_options_example.csv");
+///std::fs::write(&path, "10,5,1\n20,5,0\n30,5,1\n").unwrap();
+///
+///let options = CsvOptions { has_header: false, normalize: true };
+///let (inputs, outputs) = load_csv_with_options(path.to_str().unwrap(), &[2], options).unwrap();
+///assert_eq!(inputs, vec![vec![0.0, 0.0], vec![0.5, 0.0], vec![1.0, 0.0]]);
+///assert_eq!(outputs, vec![vec![1.0], vec![0.0], vec![1.0]]);
+///```
+pub fn load_csv_with_options(path: &str, target_columns: &[usize], options: CsvOptions) -> Result<Dataset, DataError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(options.has_header)
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+
+    let mut features: Vec<Vec<f32>> = vec![];
+    let mut targets: Vec<Vec<f32>> = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |position| position.line());
+        if let Some(&column) = target_columns.iter().find(|&&column| column >= record.len()) {
+            return Err(DataError::MissingColumn { column, columns: record.len() });
+        }
+
+        let mut values = Vec::with_capacity(record.len());
+        for (column, field) in record.iter().enumerate() {
+            let value = field.parse::<f32>().map_err(|_| DataError::Parse { line, column, value: field.to_string() })?;
+            values.push(value);
+        }
+
+        targets.push(target_columns.iter().map(|&column| values[column]).collect());
+        features.push(values.into_iter().enumerate().filter(|(column, _)| !target_columns.contains(column)).map(|(_, value)| value).collect());
+    }
+
+    if options.normalize {
+        normalize_columns(&mut features);
+    }
+    Ok((features, targets))
+}
+
+//Min-max scales every column to [0, 1] in place
+fn normalize_columns(rows: &mut [Vec<f32>]) {
+    let columns = rows.first().map_or(0, |row| row.len());
+    for column in 0..columns {
+        let min = rows.iter().map(|row| row[column]).fold(f32::INFINITY, f32::min);
+        let max = rows.iter().map(|row| row[column]).fold(f32::NEG_INFINITY, f32::max);
+        for row in rows.iter_mut() {
+            row[column] = if max > min { (row[column] - min) / (max - min) } else { 0.0 };
+        }
+    }
+}
+
+///Shuffles a dataset with an RNG seeded by `seed` and splits it into a training set and a test
+///set holding `test_fraction` of the samples (rounded), every input staying paired with its
+///target. The same seed always gives the same split
+///
+///`test_fraction` is clamped to [0, 1], so 0 puts every sample in the training set and 1 every
+///sample in the test set
+///
+///Returns DataError::SampleCountMismatch if there aren't as many targets as inputs
+///
+///# Example
+///
+///```
+///use triton_grow::network::{data::train_test_split, error::DataError};
+///
+///let inputs: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32]).collect();
+///let targets: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32 * 2.0]).collect();
+///
+///let (train_in, train_out, test_in, test_out) = train_test_split(&inputs, &targets, 0.3, 7).unwrap();
+///assert_eq!((train_in.len(), test_in.len()), (7, 3));
+///for (input, target) in train_in.iter().zip(train_out.iter()).chain(test_in.iter().zip(test_out.iter())) {
+///    assert_eq!(target[0], input[0] * 2.0);
+///}
+///assert_eq!(train_test_split(&inputs, &targets, 0.3, 7).unwrap().2, test_in);
+///
+///assert_eq!(train_test_split(&inputs, &targets, 0.0, 7).unwrap().2.len(), 0);
+///assert_eq!(train_test_split(&inputs, &targets, 1.0, 7).unwrap().0.len(), 0);
+///assert_eq!(train_test_split(&inputs, &targets[..9], 0.3, 7), Err(DataError::SampleCountMismatch { inputs: 10, targets: 9 }));
+///```
+pub fn train_test_split(inputs: &[Vec<f32>], targets: &[Vec<f32>], test_fraction: f32, seed: u64) -> Result<TrainTestSplit, DataError> {
+    if inputs.len() != targets.len() {
+        return Err(DataError::SampleCountMismatch { inputs: inputs.len(), targets: targets.len() });
+    }
+    let samples = inputs.len();
+    let test_fraction = if test_fraction.is_nan() { 0.0 } else { test_fraction.clamp(0.0, 1.0) };
+    let test_size = ((samples as f32 * test_fraction).round() as usize).min(samples);
+
+    let mut order: Vec<usize> = (0..samples).collect();
+    order.shuffle(&mut StdRng::seed_from_u64(seed));
+    let (test_order, train_order) = order.split_at(test_size);
+
+    let gather = |indices: &[usize], data: &[Vec<f32>]| -> Vec<Vec<f32>> {
+        indices.iter().map(|&index| data[index].clone()).collect()
+    };
+    Ok((gather(train_order, inputs), gather(train_order, targets), gather(test_order, inputs), gather(test_order, targets)))
+}
+
+///Encodes class labels as one-hot targets, a Vec of `num_classes` values per label that's 1 at
+///the label and 0 everywhere else, ready for a SOFTMAX output trained with Loss::CROSSENTROPY
+///
+///Panics if a label isn't below `num_classes`
+///
+///# Example
+///
+///```
+///use triton_grow::network::data::{one_hot, argmax_decode};
+///
+///let labels = vec![2, 0, 1, 2];
+///let targets = one_hot(&labels, 3);
+///assert_eq!(targets[0], vec![0.0, 0.0, 1.0]);
+///assert_eq!(targets[1], vec![1.0, 0.0, 0.0]);
+///assert_eq!(argmax_decode(&targets), labels);
+///```
+pub fn one_hot(labels: &[usize], num_classes: usize) -> Vec<Vec<f32>> {
+    labels.iter().map(|&label| {
+        if label >= num_classes {
+            panic!("Error attempting to one-hot encode label {} into only {} classes", label, num_classes);
+        }
+        let mut res = vec![0.0; num_classes];
+        res[label] = 1.0;
+        res
+    }).collect()
+}
+
+///Turns a univariate series into supervised pairs for forecasting, every input is `window`
+///consecutive values and its target the `horizon` values right after them. Windows start at every
+///index of the series, so neighbouring pairs overlap
+///
+///Both are empty when the series is shorter than `window + horizon`
+///
+///# Example
+///
+///```
+///use triton_grow::network::data::sliding_windows;
+///
+///let ramp: Vec<f32> = (0..6).map(|i| i as f32).collect();
+///let (inputs, targets) = sliding_windows(&ramp, 3, 2);
+///assert_eq!(inputs, vec![vec![0.0, 1.0, 2.0], vec![1.0, 2.0, 3.0]]);
+///assert_eq!(targets, vec![vec![3.0, 4.0], vec![4.0, 5.0]]);
+///
+///let (inputs, targets) = sliding_windows(&ramp, 5, 2);
+///assert!(inputs.is_empty() && targets.is_empty());
+///```
+pub fn sliding_windows(series: &[f32], window: usize, horizon: usize) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+    let span = window + horizon;
+    if series.len() < span {
+        return (vec![], vec![]);
+    }
+    (0..=series.len() - span).map(|start| {
+        (series[start..start + window].to_vec(), series[start + window..start + span].to_vec())
+    }).unzip()
+}
+
+///Smooths a one-hot target so the true class is `1 - epsilon` and every other class shares
+///`epsilon` evenly, `epsilon / (K - 1)` each. A target with a single value is treated as a binary
+///label, where the implied other class takes all of `epsilon`
+///
+///# Example
+///
+///```
+///use triton_grow::network::data::smooth_labels;
+///
+///assert_eq!(smooth_labels(&[0.0, 1.0, 0.0], 0.1), vec![0.05, 0.9, 0.05]);
+///assert_eq!(smooth_labels(&[1.0], 0.2), vec![0.8]);
+///assert_eq!(smooth_labels(&[0.0], 0.2), vec![0.2]);
+///```
+pub fn smooth_labels(target: &[f32], epsilon: f32) -> Vec<f32> {
+    let others = target.len().saturating_sub(1).max(1) as f32;
+    target.iter().map(|value| value * (1.0 - epsilon) + (1.0 - value) * epsilon / others).collect()
+}
+
+///Decodes every output (or one-hot target) into the index of its largest value, the inverse of
+///one_hot. Ties go to the lowest index
+pub fn argmax_decode(outputs: &[Vec<f32>]) -> Vec<usize> {
+    outputs.iter().map(|output| argmax(output)).collect()
+}
+
+///The index of the largest value in `values`, the first one wins ties
+pub(crate) fn argmax(values: &[f32]) -> usize {
+    let mut best = 0;
+    for (i, value) in values.iter().enumerate() {
+        if *value > values[best] {
+            best = i;
+        }
+    }
+    best
+}
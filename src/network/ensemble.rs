@@ -0,0 +1,120 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{network::{Network, check_sample_count}, error::NetworkError};
+
+///A group of networks whose predictions are combined, the outputs of every member are averaged
+///and classes are decided by a majority vote
+///
+///# Example
+///
+///```
+///use triton_grow::network::{network::Network, ensemble::Ensemble, activations::Activations, layer::layers::LayerTypes, modes::Task};
+///
+///let inputs: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32 / 20.0]).collect();
+///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| vec![(input[0] > 0.5) as u8 as f32]).collect();
+///
+///let mut new_net = Network::new(1);
+///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+///new_net.set_task(Task::CLASSIFICATION);
+///new_net.compile();
+///
+///let mut ensemble = Ensemble::from_template(&new_net, 5);
+///ensemble.train_bagged(&inputs, &outputs, 200, 7).unwrap();
+///
+///assert_eq!(ensemble.predict(vec![0.9]).unwrap().len(), 1);
+///assert_eq!(ensemble.predict_class(vec![0.9]).unwrap(), 1);
+///assert_eq!(ensemble.predict_class(vec![0.1]).unwrap(), 0);
+///```
+#[derive(Clone)]
+pub struct Ensemble {
+    pub members: Vec<Network>,
+}
+
+impl Ensemble {
+    pub fn new(members: Vec<Network>) -> Ensemble {
+        Ensemble { members }
+    }
+    ///An ensemble of `count` copies of a compiled `template`, each with its own freshly
+    ///initialized weights. Members of a seeded template each get their own seed derived from the
+    ///template's, so they differ from each other but come out the same every time
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, ensemble::Ensemble, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new_seeded(2, 5);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let first = Ensemble::from_template(&new_net, 3);
+    ///let second = Ensemble::from_template(&new_net, 3);
+    ///let weights = |ensemble: &Ensemble| -> Vec<Vec<f32>> { ensemble.members.iter().map(|member| member.layers[0].parameters()).collect() };
+    ///assert_ne!(weights(&first)[0], weights(&first)[1]);
+    ///assert_ne!(weights(&first)[1], weights(&first)[2]);
+    ///assert_eq!(weights(&first), weights(&second));
+    ///```
+    pub fn from_template(template: &Network, count: usize) -> Ensemble {
+        let members = (0..count).map(|index| {
+            let mut member = template.clone();
+            if let Some(seed) = template.seed() {
+                //Shifted past the per layer offsets compile adds, so no two members share a layer seed
+                member.set_seed(seed.wrapping_add((index as u64) << 16));
+            }
+            member.reset_weights();
+            member
+        }).collect();
+        Ensemble { members }
+    }
+    ///Trains every member for `epochs` epochs on its own bootstrap sample of the data, as many
+    ///samples as there are inputs drawn with replacement. The same `seed` always draws the same
+    ///samples
+    pub fn train_bagged(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>], epochs: usize, seed: u64) -> Result<(), NetworkError> {
+        check_sample_count(inputs.len(), targets.len())?;
+        let mut rng = StdRng::seed_from_u64(seed);
+        for member in self.members.iter_mut() {
+            let sample: Vec<usize> = (0..inputs.len()).map(|_| rng.gen_range(0..inputs.len())).collect();
+            let sample_in: Vec<Vec<f32>> = sample.iter().map(|&index| inputs[index].clone()).collect();
+            let sample_out: Vec<Vec<f32>> = sample.iter().map(|&index| targets[index].clone()).collect();
+            member.fit_seeded(&sample_in, &sample_out, epochs, rng.gen())?;
+        }
+        Ok(())
+    }
+    ///The average of every member's outputs for `input`
+    ///
+    ///Returns NetworkError::EmptyNetwork when the ensemble has no members
+    pub fn predict(&mut self, input: Vec<f32>) -> Result<Vec<f32>, NetworkError> {
+        if self.members.is_empty() {
+            return Err(NetworkError::EmptyNetwork);
+        }
+        let mut res: Vec<f32> = vec![];
+        for member in self.members.iter_mut() {
+            let outputs = member.predict(input.clone())?;
+            if res.is_empty() {
+                res = vec![0.0; outputs.len()];
+            }
+            res.iter_mut().zip(outputs.iter()).for_each(|(sum, output)| *sum += output);
+        }
+        let count = self.members.len() as f32;
+        Ok(res.into_iter().map(|sum| sum / count).collect())
+    }
+    ///The class most members decide on for `input`, ties go to the lowest class
+    ///
+    ///Every member has to be a classifier, see Network::predict_class
+    pub fn predict_class(&mut self, input: Vec<f32>) -> Result<usize, NetworkError> {
+        if self.members.is_empty() {
+            return Err(NetworkError::EmptyNetwork);
+        }
+        let mut votes: Vec<usize> = vec![];
+        for member in self.members.iter_mut() {
+            let class = member.predict_class(input.clone())?;
+            if votes.len() <= class {
+                votes.resize(class + 1, 0);
+            }
+            votes[class] += 1;
+        }
+        let most = *votes.iter().max().unwrap_or(&0);
+        Ok(votes.iter().position(|&count| count == most).unwrap_or(0))
+    }
+}
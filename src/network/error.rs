@@ -0,0 +1,174 @@
+use std::fmt;
+
+///Errors that can be raised while running data through a Network
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkError {
+    ///The input supplied does not match the width of the network's input layer
+    InputSizeMismatch { expected: usize, got: usize },
+    ///The target supplied does not match the width of the network's output layer
+    OutputSizeMismatch { expected: usize, got: usize },
+    ///The final layer of the network cannot act as an output layer (it has no activation)
+    InvalidOutputLayer,
+    ///A network was built without any layers
+    EmptyNetwork,
+    ///A network was built without declaring the size of its input
+    MissingInputSize,
+    ///The layer at `index` can't be constructed
+    InvalidLayer { index: usize, reason: String },
+    ///A class label was asked for from a network that isn't set to Task::CLASSIFICATION
+    NotAClassifier,
+    ///A network's loss or weights stopped being finite (NaN or infinite) during the given epoch,
+    ///counting from 1
+    DivergedAtEpoch(usize),
+    ///A sample passed to Network::fit_heads has no target for the named branch
+    MissingHeadTarget(String),
+    ///An Embedding layer was fed a token that isn't a whole number below its vocabulary size
+    InvalidToken { token: f32, vocab_size: usize },
+    ///A target for Loss::SPARSECROSSENTROPY isn't the whole numbered index of one of the output
+    ///layer's `classes` classes
+    InvalidClassLabel { label: f32, classes: usize },
+    ///Every training input needs a target, but there are a different number of each
+    SampleCountMismatch { inputs: usize, targets: usize },
+    ///Reading or writing a saved network failed
+    Io(String),
+    ///A network could not be converted to or from its saved representation
+    Serialization(String),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::InputSizeMismatch { expected, got } => write!(f, "Input shape does not match input layer shape, expected {} but got {}", expected, got),
+            NetworkError::OutputSizeMismatch { expected, got } => write!(f, "Target shape does not match output layer shape, expected {} but got {}", expected, got),
+            NetworkError::InvalidOutputLayer => write!(f, "Output layer is not a dense layer"),
+            NetworkError::EmptyNetwork => write!(f, "Network has no layers"),
+            NetworkError::MissingInputSize => write!(f, "Network has no input size"),
+            NetworkError::InvalidLayer { index, reason } => write!(f, "Layer {} is invalid: {}", index, reason),
+            NetworkError::NotAClassifier => write!(f, "Network is not a classifier, set its task to Task::CLASSIFICATION"),
+            NetworkError::DivergedAtEpoch(epoch) => write!(f, "Training diverged at epoch {}, the network's loss or weights are no longer finite", epoch),
+            NetworkError::MissingHeadTarget(branch_id) => write!(f, "A sample is missing a target for the branch {}", branch_id),
+            NetworkError::InvalidToken { token, vocab_size } => write!(f, "Token {} is not in a vocabulary of {} tokens", token, vocab_size),
+            NetworkError::InvalidClassLabel { label, classes } => write!(f, "Class label {} is not one of the {} classes of the output layer", label, classes),
+            NetworkError::SampleCountMismatch { inputs, targets } => write!(f, "Every input needs a target, but got {} inputs and {} targets", inputs, targets),
+            NetworkError::Io(err) => write!(f, "Unable to access saved network: {}", err),
+            NetworkError::Serialization(err) => write!(f, "Unable to serialize network: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl From<std::io::Error> for NetworkError {
+    fn from(err: std::io::Error) -> Self {
+        NetworkError::Io(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for NetworkError {
+    fn from(err: serde_json::Error) -> Self {
+        NetworkError::Serialization(err.to_string())
+    }
+}
+
+///Errors that can be raised by Matrix operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    ///The left hand side needs as many columns as the right hand side has rows, the shapes are
+    ///(rows, columns)
+    DimensionMismatch { lhs: (usize, usize), rhs: (usize, usize) },
+    ///Matrices placed side by side need the same number of rows
+    RowCountMismatch { lhs: (usize, usize), rhs: (usize, usize) },
+    ///Matrices stacked on top of each other need the same number of columns
+    ColumnCountMismatch { lhs: (usize, usize), rhs: (usize, usize) },
+    ///A reshaped Matrix needs as many values as the original, the shapes are (rows, columns)
+    ReshapeMismatch { from: (usize, usize), to: (usize, usize) },
+    ///A Matrix read from raw bytes needs exactly 4 bytes for every value
+    ByteLengthMismatch { expected: usize, got: usize },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { lhs, rhs } => write!(f, "Error attempting to multiply two matrices with different dimensions \nMatrix A: {} x {}\nMatrix B: {} x {}", lhs.0, lhs.1, rhs.0, rhs.1),
+            MatrixError::RowCountMismatch { lhs, rhs } => write!(f, "Error attempting to concatenate two matrices horizontally with a different number of rows \nMatrix A: {} x {}\nMatrix B: {} x {}", lhs.0, lhs.1, rhs.0, rhs.1),
+            MatrixError::ColumnCountMismatch { lhs, rhs } => write!(f, "Error attempting to concatenate two matrices vertically with a different number of columns \nMatrix A: {} x {}\nMatrix B: {} x {}", lhs.0, lhs.1, rhs.0, rhs.1),
+            MatrixError::ReshapeMismatch { from, to } => write!(f, "Error attempting to reshape a matrix into a different number of values \nMatrix: {} x {}\nShape: {} x {}", from.0, from.1, to.0, to.1),
+            MatrixError::ByteLengthMismatch { expected, got } => write!(f, "Error attempting to read a matrix from raw bytes of the wrong length \nExpected: {} bytes\nGot: {} bytes", expected, got),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+///Errors that can be raised while loading or splitting a dataset
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError {
+    ///The file couldn't be read
+    Io(String),
+    ///The file isn't valid CSV, or its rows don't all have the same number of columns
+    Csv(String),
+    ///The value at `line` and `column` isn't a number
+    Parse { line: u64, column: usize, value: String },
+    ///A target column was asked for that the rows don't have
+    MissingColumn { column: usize, columns: usize },
+    ///Every input needs a target, but there are a different number of each
+    SampleCountMismatch { inputs: usize, targets: usize },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Io(err) => write!(f, "Unable to read dataset: {}", err),
+            DataError::Csv(err) => write!(f, "Unable to parse dataset: {}", err),
+            DataError::Parse { line, column, value } => write!(f, "Value {:?} on line {} column {} is not a number", value, line, column),
+            DataError::MissingColumn { column, columns } => write!(f, "Target column {} is out of range, the dataset only has {} columns", column, columns),
+            DataError::SampleCountMismatch { inputs, targets } => write!(f, "Dataset has {} inputs but {} targets", inputs, targets),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+impl From<std::io::Error> for DataError {
+    fn from(err: std::io::Error) -> Self {
+        DataError::Io(err.to_string())
+    }
+}
+
+impl From<csv::Error> for DataError {
+    fn from(err: csv::Error) -> Self {
+        DataError::Csv(err.to_string())
+    }
+}
+
+///Errors that can be raised while exporting a network to another format
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportError {
+    ///The network has no compiled layers to export
+    EmptyNetwork,
+    ///The layer at `index` has no equivalent in the exported format
+    UnsupportedLayer { index: usize, layer: String },
+    ///The activation of the layer at `index` has no equivalent in the exported format
+    UnsupportedActivation { index: usize, activation: String },
+    ///The exported file couldn't be written
+    Io(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::EmptyNetwork => write!(f, "Network has no compiled layers to export"),
+            ExportError::UnsupportedLayer { index, layer } => write!(f, "Layer {} ({}) can't be exported", index, layer),
+            ExportError::UnsupportedActivation { index, activation } => write!(f, "The {} activation of layer {} can't be exported", activation, index),
+            ExportError::Io(err) => write!(f, "Unable to write exported network: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<std::io::Error> for ExportError {
+    fn from(err: std::io::Error) -> Self {
+        ExportError::Io(err.to_string())
+    }
+}
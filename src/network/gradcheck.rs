@@ -0,0 +1,118 @@
+use super::{layer::layers::Layer, input::Input, matrix::Matrix};
+
+///Checks a layer's backward against numerical gradients, returning the largest relative error
+///between the two across every trainable parameter
+///
+///The loss checked against is half the sum of the layer's squared outputs. Every parameter is
+///nudged by `epsilon` in both directions to estimate its gradient with central differences,
+///which is compared to the gradient backward accumulates for the same input. Errors well below
+///1e-2 (f32 is only so precise) mean backward agrees with forward
+///
+///The layer's parameters are left as they were, but its accumulated gradients aren't cleared and
+///any state it carries between passes is reset
+///
+///# Example
+///
+///```
+///use triton_grow::network::{gradcheck::check_layer, layer::dense::Dense, activations::Activations};
+///
+///let mut dense = Dense::new(3, 2, Activations::SIGMOID, 0.1);
+///let error = check_layer(&mut dense, &vec![0.5, -0.3, 0.8], 1e-2);
+///assert!(error < 1e-2);
+///```
+pub fn check_layer(layer: &mut dyn Layer, input: &dyn Input, epsilon: f32) -> f32 {
+    //Stateful layers would otherwise carry every forward pass into the next one
+    layer.reset_state();
+    layer.forward(input);
+    //Read after the first pass, layers that size their inputs lazily only have weights by now
+    let parameters = layer.parameters();
+    let before = layer.parameter_gradients();
+    //The errors backward expects are the negative gradient of the loss, -output for this loss,
+    //laid out the same way the layer keeps its own output
+    let errors = Matrix::from(layer.get_data().to_param_2d()) * -1.0;
+    let data = Matrix::from(input.to_param_2d()).transpose();
+    layer.backward(layer.update_gradient(), Box::new(errors), Box::new(data));
+    let after = layer.parameter_gradients();
+
+    let mut max_error: f32 = 0.0;
+    for i in 0..parameters.len() {
+        let mut nudged = parameters.clone();
+
+        nudged[i] = parameters[i] + epsilon;
+        layer.set_parameters(&nudged);
+        layer.reset_state();
+        let loss_up = half_squared_sum(&layer.forward(input).to_param());
+
+        nudged[i] = parameters[i] - epsilon;
+        layer.set_parameters(&nudged);
+        layer.reset_state();
+        let loss_down = half_squared_sum(&layer.forward(input).to_param());
+
+        let numerical = (loss_up - loss_down) / (2.0 * epsilon);
+        let analytic = -(after[i] - before.get(i).copied().unwrap_or(0.0));
+
+        let scale = (numerical.abs() + analytic.abs()).max(1e-6);
+        max_error = max_error.max((numerical - analytic).abs() / scale);
+    }
+    layer.set_parameters(&parameters);
+
+    max_error
+}
+
+///Checks the errors a layer's backward passes to the layer before it against numerical gradients
+///of its input, returning the largest relative error between the two across every input value
+///
+///Uses the same loss as check_layer. The errors are compared in the order the input's values come
+///in, so a single sample (or a layer that returns its errors in the input's own layout) is
+///expected
+///
+///# Example
+///
+///Every activation takes its derivative of the pre-activation values, so each one agrees. The
+///weights are seeded so no RELU sits close enough to its kink at 0 to throw off the estimate
+///
+///```
+///use triton_grow::network::{gradcheck::{check_layer, check_input}, layer::dense::Dense, activations::Activations, rng};
+///
+///for activation in [Activations::SIGMOID, Activations::TANH, Activations::RELU, Activations::LEAKYRELU(0.1), Activations::GELU, Activations::ELU(1.0), Activations::SWISH, Activations::MISH] {
+///    let mut dense = rng::with_seed(0, || Dense::new(3, 2, activation, 0.1));
+///    let input = vec![0.5, -0.3, 0.8];
+///    assert!(check_layer(&mut dense, &input, 1e-2) < 1e-2, "{:?}", activation);
+///    assert!(check_input(&mut dense, &input, 1e-2) < 1e-2, "{:?}", activation);
+///}
+///```
+pub fn check_input(layer: &mut dyn Layer, input: &dyn Input, epsilon: f32) -> f32 {
+    layer.reset_state();
+    layer.forward(input);
+    let errors = Matrix::from(layer.get_data().to_param_2d()) * -1.0;
+    let data = Matrix::from(input.to_param_2d()).transpose();
+    let analytic = layer.backward(layer.update_gradient(), Box::new(errors), Box::new(data)).to_param();
+
+    let values = Matrix::from(input.to_param_2d());
+    let mut max_error: f32 = 0.0;
+    for i in 0..values.rows * values.columns {
+        let (row, column) = (i / values.columns, i % values.columns);
+        let mut nudged = values.clone();
+
+        nudged.data[row][column] = values.data[row][column] + epsilon;
+        layer.reset_state();
+        let loss_up = half_squared_sum(&layer.forward(&nudged).to_param());
+
+        nudged.data[row][column] = values.data[row][column] - epsilon;
+        layer.reset_state();
+        let loss_down = half_squared_sum(&layer.forward(&nudged).to_param());
+
+        let numerical = (loss_up - loss_down) / (2.0 * epsilon);
+        //The errors are the negative gradient
+        let analytic = -analytic.get(i).copied().unwrap_or(0.0);
+
+        let scale = (numerical.abs() + analytic.abs()).max(1e-6);
+        max_error = max_error.max((numerical - analytic).abs() / scale);
+    }
+
+    max_error
+}
+
+fn half_squared_sum(values: &[f32]) -> f32 {
+    values.iter().map(|value| value * value).sum::<f32>() / 2.0
+}
@@ -0,0 +1,35 @@
+use serde::{Serialize, Deserialize};
+
+use super::matrix::Matrix;
+
+///How the weights of a layer are first filled in
+///
+///He suits layers using RELU (or its variants), Xavier suits SIGMOID and TANH layers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Initializer{
+    ///Uniformly random values between -1 and 1
+    #[default]
+    Uniform,
+    ///Normally distributed values scaled by sqrt(2 / fan_in)
+    He,
+    ///Normally distributed values scaled by sqrt(1 / fan_in)
+    Xavier,
+}
+
+impl Initializer{
+    ///A new `rows` x `cols` weight Matrix, every column is one input so `cols` is the fan in
+    pub fn weights(&self, rows: usize, cols: usize) -> Matrix {
+        match self {
+            Initializer::Uniform => Matrix::new_random(rows, cols),
+            Initializer::He => Matrix::new_he(rows, cols, cols),
+            Initializer::Xavier => Matrix::new_xavier(rows, cols, cols),
+        }
+    }
+    ///A new `rows` x 1 bias Matrix, scaled initializations start their biases at 0
+    pub fn biases(&self, rows: usize) -> Matrix {
+        match self {
+            Initializer::Uniform => Matrix::new_random(rows, 1),
+            Initializer::He | Initializer::Xavier => Matrix::new_empty(rows, 1),
+        }
+    }
+}
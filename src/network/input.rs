@@ -98,15 +98,109 @@ impl Input for Matrix3D {
         self.data.clone()
     }
     fn shape(&self) -> (usize, usize, usize) {
-        self.shape()
+        (self.rows, self.columns, self.layers)
     }
     fn to_box(&self) -> Box<dyn Input> {
         Box::new(self.to_param_3d())
     }
 }
 
-impl Into<Box<dyn Input>> for Vec<f32> {
-    fn into(self) -> Box<dyn Input> {
-        Box::new(self)
+///A 2D grayscale image, every inner Vec is one row of `width` pixels
+///
+///to_param_2d keeps the rows and columns of the image so it can be fed straight into pooling and
+///flatten layers, while to_param flattens it row by row
+///
+///# Example
+///
+///```
+///use triton_grow::network::{input::{Image, Input}, layer::{layers::Layer, pool::MaxPool, flatten::Flatten, dense::Dense}, activations::Activations};
+///
+///let image = Image::new(vec![
+///    vec![0.0, 0.1, 0.2, 0.3],
+///    vec![0.4, 0.5, 0.6, 0.7],
+///    vec![0.8, 0.9, 1.0, 0.0],
+///    vec![0.1, 0.2, 0.3, 0.4]]);
+///assert_eq!((image.width, image.height), (4, 4));
+///assert_eq!(image.to_param()[4], 0.4);
+///
+///let mut pool = MaxPool::new((2, 2), 2);
+///let mut flatten = Flatten::new();
+///let mut dense = Dense::new(4, 2, Activations::SIGMOID, 0.1);
+///
+///let pooled = pool.forward(&image);
+///let flat = flatten.forward(&*pooled);
+///assert_eq!(dense.forward(&*flat).to_param().len(), 2);
+///```
+#[derive(Clone, Debug, Default)]
+pub struct Image{
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Vec<f32>>,
+}
+
+impl Image{
+    ///Wraps rows of pixels as an Image, the width is taken from the first row
+    pub fn new(pixels: Vec<Vec<f32>>) -> Image {
+        Image {
+            width: pixels.first().map_or(0, |row| row.len()),
+            height: pixels.len(),
+            pixels,
+        }
+    }
+}
+
+impl Input for Image {
+    fn to_param(&self) -> Vec<f32> {
+        self.pixels.clone().into_iter().flatten().collect::<Vec<f32>>()
+    }
+    fn to_param_2d(&self) -> Vec<Vec<f32>> {
+        self.pixels.clone()
+    }
+    fn to_param_3d(&self) -> Vec<Vec<Vec<f32>>> {
+        vec![self.pixels.clone()]
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.height, self.width, 0)
+    }
+    fn to_box(&self) -> Box<dyn Input> {
+        Box::new(self.clone())
+    }
+}
+
+///A sequence of token indices for an Embedding layer, every index picks one row of the
+///embedding matrix
+///
+///# Example
+///
+///```
+///use triton_grow::network::input::{Tokens, Input};
+///
+///let tokens = Tokens(vec![3, 0, 7]);
+///assert_eq!(tokens.to_param(), vec![3.0, 0.0, 7.0]);
+///```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tokens(pub Vec<usize>);
+
+impl Input for Tokens {
+    fn to_param(&self) -> Vec<f32> {
+        self.0.iter().map(|&token| token as f32).collect()
+    }
+    fn to_param_2d(&self) -> Vec<Vec<f32>> {
+        vec![self.to_param()]
+    }
+    fn to_param_3d(&self) -> Vec<Vec<Vec<f32>>> {
+        vec![self.to_param_2d()]
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.0.len(), 1, 0)
+    }
+    fn to_box(&self) -> Box<dyn Input> {
+        Box::new(self.clone())
+    }
+}
+
+impl From<Vec<f32>> for Box<dyn Input> {
+    fn from(value: Vec<f32>) -> Self {
+        Box::new(value)
     }
 }
@@ -0,0 +1,251 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, input::Input, modes::Mode};
+
+use super::layers::Layer;
+
+//How much of every new batch's statistics is blended into the running statistics
+const MOMENTUM: f32 = 0.1;
+const EPSILON: f32 = 1e-5;
+///The learning rate the scale and shift of a BATCHNORM layer train with
+pub const DEFAULT_BATCHNORM_LEARNING_RATE: f32 = 0.01;
+
+///A Batch Normalization layer, normalizes every feature to a mean of 0 and a variance of 1 and
+///then applies a learnable scale (gamma) and shift (beta)
+///Implements the Layer trait
+///
+///While training every row of the input is one sample of the batch and the batch's own mean and
+///variance are used, which also update the running statistics. Outside of training the running
+///statistics are used instead, so a single sample can be normalized. A batch of a single sample
+///has no variance of its own, so while training it (like every sample a Network feeds forward)
+///is normalized with the running statistics, which it then updates
+///
+///# Example
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, batchnorm::BatchNorm}, input::Input};
+///
+///let mut norm = BatchNorm::new(2, 0.01);
+///let batch: Vec<Vec<f32>> = (0..64).map(|i| vec![i as f32 * 3.0 + 10.0, (i % 5) as f32 - 40.0]).collect();
+///
+///let res = norm.forward(&batch).to_param_2d();
+///for feature in 0..2 {
+///    let values: Vec<f32> = res.iter().map(|sample| sample[feature]).collect();
+///    let mean = values.iter().sum::<f32>() / values.len() as f32;
+///    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / values.len() as f32;
+///    assert!(mean.abs() < 1e-4);
+///    assert!((variance - 1.0).abs() < 1e-3);
+///}
+///```
+///
+///Inside of a network, which trains on one sample at a time
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+///
+///let inputs: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32 / 2.0, 10.0 - i as f32]).collect();
+///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| vec![(input[0] > 5.0) as u8 as f32]).collect();
+///
+///let mut new_net = Network::new_seeded(2, 4);
+///new_net.add_layer(LayerTypes::DENSE(6, Activations::LEAKYRELU(1.0), 0.01));
+///new_net.add_layer(LayerTypes::BATCHNORM);
+///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.01));
+///new_net.compile();
+///
+///let history = new_net.fit_seeded(&inputs, &outputs, 100, 4).unwrap();
+///assert!(history.loss[99] < history.loss[0] / 2.0);
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BatchNorm{
+    pub gamma: Matrix,
+    pub beta: Matrix,
+    pub running_mean: Matrix,
+    pub running_variance: Matrix,
+    pub data: Matrix,
+    mode: Mode,
+    learning_rate: f32,
+    loss: f32,
+    #[serde(default)]
+    frozen: bool,
+
+    #[serde(skip)]
+    normalized: Matrix,
+    #[serde(skip)]
+    variance: Matrix,
+    //Whether the last forward pass normalized with its own batch statistics, which backward has
+    //to differentiate through, rather than the running statistics
+    #[serde(skip)]
+    batch_statistics: bool,
+    #[serde(skip)]
+    gamma_gradient: Matrix,
+    #[serde(skip)]
+    beta_gradient: Matrix,
+}
+
+impl BatchNorm{
+    pub fn new(features: usize, learning_rate: f32) -> BatchNorm {
+        BatchNorm {
+            gamma: Matrix::new_empty(features, 1) + 1.0,
+            beta: Matrix::new_empty(features, 1),
+            running_mean: Matrix::new_empty(features, 1),
+            running_variance: Matrix::new_empty(features, 1) + 1.0,
+            data: Matrix::new_empty(0, 0),
+            mode: Mode::TRAIN,
+            learning_rate,
+            loss: 0.0,
+            frozen: false,
+            normalized: Matrix::new_empty(0, 0),
+            variance: Matrix::new_empty(features, 1),
+            batch_statistics: false,
+            gamma_gradient: Matrix::new_empty(features, 1),
+            beta_gradient: Matrix::new_empty(features, 1),
+        }
+    }
+}
+
+#[typetag::serde]
+impl Layer for BatchNorm{
+    ///Normalizes every feature across the batch (or with the running statistics outside of
+    ///training), then scales and shifts it
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = Matrix::from(inputs.to_param_2d()).transpose();
+        //The width of the layer before can be unknown until data flows through the network
+        if self.gamma.rows != input.rows {
+            *self = BatchNorm { mode: self.mode, frozen: self.frozen, ..BatchNorm::new(input.rows, self.learning_rate) };
+        }
+        let samples = input.columns as f32;
+
+        let mut mean = self.running_mean.clone();
+        let mut variance = self.running_variance.clone();
+        self.batch_statistics = self.mode == Mode::TRAIN && input.columns > 1;
+        if self.batch_statistics {
+            for feature in 0..input.rows {
+                mean.data[feature][0] = input.data[feature].iter().sum::<f32>() / samples;
+                variance.data[feature][0] = input.data[feature].iter().map(|value| (value - mean.data[feature][0]).powi(2)).sum::<f32>() / samples;
+            }
+            self.running_mean = self.running_mean.clone() * (1.0 - MOMENTUM) + &(mean.clone() * MOMENTUM);
+            self.running_variance = self.running_variance.clone() * (1.0 - MOMENTUM) + &(variance.clone() * MOMENTUM);
+        } else if self.mode == Mode::TRAIN {
+            //A single sample is normalized with the running statistics from before it, which
+            //then move towards it
+            for feature in 0..input.rows {
+                let deviation = input.data[feature][0] - mean.data[feature][0];
+                self.running_mean.data[feature][0] += MOMENTUM * deviation;
+                self.running_variance.data[feature][0] = self.running_variance.data[feature][0] * (1.0 - MOMENTUM) + MOMENTUM * deviation.powi(2);
+            }
+        }
+
+        self.normalized = Matrix::new_empty(input.rows, input.columns);
+        self.data = Matrix::new_empty(input.rows, input.columns);
+        for feature in 0..input.rows {
+            let std_dev = (variance.data[feature][0] + EPSILON).sqrt();
+            for sample in 0..input.columns {
+                let normalized = (input.data[feature][sample] - mean.data[feature][0]) / std_dev;
+                self.normalized.data[feature][sample] = normalized;
+                self.data.data[feature][sample] = self.gamma.data[feature][0] * normalized + self.beta.data[feature][0];
+            }
+        }
+        self.variance = variance;
+
+        Box::new(self.data.transpose())
+    }
+    ///Accumulates the gradients of gamma and beta and returns the errors of the input, which
+    ///account for every sample of the batch sharing the same mean and variance when the batch's
+    ///own statistics were used
+    fn backward(&mut self, _gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let errors_mat = Matrix::from(errors.to_param_2d());
+        let (features, samples) = (self.normalized.rows, self.normalized.columns);
+
+        //Gradients are skipped during serialization, so a freshly loaded layer starts out empty
+        if self.gamma_gradient.rows == 0 {
+            self.gamma_gradient = Matrix::new_empty(features, 1);
+            self.beta_gradient = Matrix::new_empty(features, 1);
+        }
+
+        let mut input_errors = Matrix::new_empty(features, samples);
+        for feature in 0..features {
+            let errors_row = &errors_mat.data[feature];
+            let normalized_row = &self.normalized.data[feature];
+
+            let error_sum = errors_row.iter().sum::<f32>();
+            let scaled_sum = errors_row.iter().zip(normalized_row.iter()).map(|(error, normalized)| error * normalized).sum::<f32>();
+            if !self.frozen {
+                self.beta_gradient.data[feature][0] += error_sum;
+                self.gamma_gradient.data[feature][0] += scaled_sum;
+            }
+
+            let scale = self.gamma.data[feature][0] / (self.variance.data[feature][0] + EPSILON).sqrt();
+            for sample in 0..samples {
+                input_errors.data[feature][sample] = if self.batch_statistics {
+                    scale * (errors_row[sample] - error_sum / samples as f32 - normalized_row[sample] * scaled_sum / samples as f32)
+                } else {
+                    scale * errors_row[sample]
+                };
+            }
+        }
+
+        let values = input_errors.to_param();
+        self.loss = values.iter().map(|error| error.powi(2)).sum::<f32>() / values.len().max(1) as f32;
+
+        Box::new(input_errors)
+    }
+    ///Moves gamma and beta along the accumulated gradients, scaled by `scale` and the learning
+    ///rate
+    fn apply_gradients(&mut self, scale: f32) {
+        if self.gamma_gradient.rows == 0 {
+            return;
+        }
+        self.gamma = self.gamma.clone() + &(self.gamma_gradient.clone() * (scale * self.learning_rate));
+        self.beta = self.beta.clone() + &(self.beta_gradient.clone() * (scale * self.learning_rate));
+
+        self.gamma_gradient = Matrix::new_empty(self.gamma.rows, 1);
+        self.beta_gradient = Matrix::new_empty(self.beta.rows, 1);
+    }
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.learning_rate = learning_rate;
+    }
+    fn set_trainable(&mut self, trainable: bool) {
+        self.frozen = !trainable;
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.gamma.rows, self.gamma.rows, 0)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        self.loss
+    }
+    fn parameter_count(&self) -> usize {
+        self.gamma.rows + self.beta.rows
+    }
+    fn parameters(&self) -> Vec<f32> {
+        [self.gamma.to_param(), self.beta.to_param()].concat()
+    }
+    fn parameters_finite(&self) -> bool {
+        self.gamma.is_finite() && self.beta.is_finite()
+    }
+    fn set_parameters(&mut self, parameters: &[f32]) {
+        let split = self.gamma.rows;
+        self.gamma = Matrix::from_slice(&parameters[..split], split, 1);
+        self.beta = Matrix::from_slice(&parameters[split..], self.beta.rows, 1);
+    }
+    fn parameter_gradients(&self) -> Vec<f32> {
+        if self.gamma_gradient.rows == 0 {
+            return vec![0.0; self.parameter_count()];
+        }
+        [self.gamma_gradient.to_param(), self.beta_gradient.to_param()].concat()
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        Box::new(Matrix::new_empty(self.data.rows, self.data.columns) + 1.0)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
@@ -1,8 +1,6 @@
 use serde::{Serialize, Deserialize};
 
-use crate::network::{matrix::Matrix,activations::Activations, input::Input, matrix3d::Matrix3D};
-
-use super::layers::Layer;
+use crate::network::{matrix::Matrix,activations::Activations, matrix3d::Matrix3D};
 
 
 
@@ -45,9 +43,9 @@ impl Convolutional{
 
         res
     }
-    pub fn convolute(&mut self, idx: usize, input: Matrix) -> Matrix{
-        let kernel = self.filter_weights.get_slice(idx);
-        let mut output = Matrix::new_empty(self.output_shape.0, self.output_shape.1);
+    pub fn convolute(&mut self, idx: usize, _input: Matrix) -> Matrix{
+        let _kernel = self.filter_weights.get_slice(idx);
+        let output = Matrix::new_empty(self.output_shape.0, self.output_shape.1);
         //slide kernel over input, summing kernel and returning resultant matrix
         
         output
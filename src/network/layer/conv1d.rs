@@ -0,0 +1,225 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, activations::Activations, input::Input};
+
+use super::layers::Layer;
+
+///A 1D Convolutional layer, slides `filters` kernels along a sequence and is meant for time-series
+///Implements the Layer trait
+///
+///Every row of the input is one channel of the sequence, so a flat vector is a single channel
+///sequence and the output of one Conv1D (one row per filter) can feed straight into another. The
+///number of channels is picked up from the first input the layer sees
+///
+///# Example
+///
+///Learning the difference between neighbouring values, an edge detector, from a known signal
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, conv1d::Conv1D}, activations::Activations, input::Input};
+///
+///let mut conv = Conv1D::new(1, 2, 1, Activations::LEAKYRELU(1.0), 0.05);
+///
+///for step in 0..2000 {
+///    let signal: Vec<f32> = (0..8).map(|i| (((step * 7 + i * 3) % 11) as f32 / 11.0)).collect();
+///    let edges: Vec<f32> = signal.windows(2).map(|pair| pair[1] - pair[0]).collect();
+///
+///    let res = conv.forward(&signal).to_param();
+///    let errors = edges.iter().zip(res.iter()).map(|(target, output)| target - output).collect::<Vec<f32>>();
+///    conv.backward(conv.update_gradient(), Box::new(errors), Box::new(signal.clone()));
+///    conv.apply_gradients(1.0);
+///}
+///
+///assert!((conv.weights.get(0, 0) + 1.0).abs() < 0.1);
+///assert!((conv.weights.get(0, 1) - 1.0).abs() < 0.1);
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Conv1D{
+    pub filters: usize,
+    pub kernel_size: usize,
+    pub stride: usize,
+    pub channels: usize,
+    //One row per filter, holding a kernel for every channel one after the other
+    pub weights: Matrix,
+    pub biases: Matrix,
+    pub data: Matrix,
+    pub activation_fn: Activations,
+    learning_rate: f32,
+    loss: f32,
+    #[serde(default)]
+    frozen: bool,
+
+    #[serde(skip)]
+    input: Matrix,
+    //The values before the activation function, which its derivative is taken of
+    #[serde(skip)]
+    pre_activation: Matrix,
+    #[serde(skip)]
+    weight_gradient: Matrix,
+    #[serde(skip)]
+    bias_gradient: Matrix,
+}
+
+impl Conv1D{
+    pub fn new(filters: usize, kernel_size: usize, stride: usize, activation: Activations, learning_rate: f32) -> Conv1D {
+        let kernel_size = kernel_size.max(1);
+        Conv1D {
+            filters,
+            kernel_size,
+            stride: stride.max(1),
+            channels: 1,
+            weights: Matrix::new_random(filters, kernel_size),
+            biases: Matrix::new_random(filters, 1),
+            data: Matrix::new_empty(0, 0),
+            activation_fn: activation,
+            learning_rate,
+            loss: 1.0,
+            frozen: false,
+            input: Matrix::new_empty(0, 0),
+            pre_activation: Matrix::new_empty(0, 0),
+            weight_gradient: Matrix::new_empty(filters, kernel_size),
+            bias_gradient: Matrix::new_empty(filters, 1),
+        }
+    }
+    fn get_res_size(&self, length: usize) -> usize {
+        if length < self.kernel_size {
+            return 0;
+        }
+        (length - self.kernel_size) / self.stride + 1
+    }
+}
+
+#[typetag::serde]
+impl Layer for Conv1D{
+    ///Correlates every filter with the input along its single spatial axis, summing over every
+    ///channel, and maps the activation function over the result
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = Matrix::from(inputs.to_param_2d());
+        if input.rows != self.channels {
+            self.channels = input.rows;
+            self.weights = Matrix::new_random(self.filters, self.channels * self.kernel_size);
+            self.weight_gradient = Matrix::new_empty(self.filters, self.channels * self.kernel_size);
+        }
+
+        let res_len = self.get_res_size(input.columns);
+        let mut output = Matrix::new_empty(self.filters, res_len);
+        for filter in 0..self.filters {
+            for t in 0..res_len {
+                let mut sum = self.biases.data[filter][0];
+                for channel in 0..self.channels {
+                    for k in 0..self.kernel_size {
+                        sum += self.weights.data[filter][channel * self.kernel_size + k] * input.data[channel][t * self.stride + k];
+                    }
+                }
+                output.data[filter][t] = sum;
+            }
+        }
+
+        self.input = input;
+        self.data = self.activation_fn.apply(output.clone());
+        self.pre_activation = output;
+        Box::new(self.data.clone())
+    }
+    ///Accumulates the gradients of every kernel and bias, and returns the errors of the input
+    ///spread back over every position each kernel touched
+    ///
+    ///The errors can arrive in any shape as long as they're ordered filter by filter
+    fn backward(&mut self, gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let gradients_mat = Matrix::from(gradients.to_param_2d());
+        let errors_flat = errors.to_param();
+        let res_len = self.data.columns;
+
+        if self.weight_gradient.rows == 0 {
+            self.weight_gradient = Matrix::new_empty(self.weights.rows, self.weights.columns);
+            self.bias_gradient = Matrix::new_empty(self.biases.rows, self.biases.columns);
+        }
+
+        let mut input_errors = Matrix::new_empty(self.input.rows, self.input.columns);
+        for filter in 0..self.filters {
+            for t in 0..res_len {
+                let delta = gradients_mat.data[filter][t] * errors_flat[filter * res_len + t];
+                if !self.frozen {
+                    self.bias_gradient.data[filter][0] += delta;
+                }
+                for channel in 0..self.channels {
+                    for k in 0..self.kernel_size {
+                        let position = t * self.stride + k;
+                        if !self.frozen {
+                            self.weight_gradient.data[filter][channel * self.kernel_size + k] += delta * self.input.data[channel][position];
+                        }
+                        input_errors.data[channel][position] += delta * self.weights.data[filter][channel * self.kernel_size + k];
+                    }
+                }
+            }
+        }
+
+        let values = input_errors.to_param();
+        self.loss = values.iter().map(|error| error.powi(2)).sum::<f32>() / values.len().max(1) as f32;
+
+        Box::new(input_errors)
+    }
+    ///Moves the kernels and biases along the accumulated gradients, scaled by `scale` and the
+    ///learning rate
+    fn apply_gradients(&mut self, scale: f32) {
+        if self.weight_gradient.rows == 0 {
+            return;
+        }
+        self.weights = self.weights.clone() + &(self.weight_gradient.clone() * (scale * self.learning_rate));
+        self.biases = self.biases.clone() + &(self.bias_gradient.clone() * (scale * self.learning_rate));
+
+        self.weight_gradient = Matrix::new_empty(self.weights.rows, self.weights.columns);
+        self.bias_gradient = Matrix::new_empty(self.biases.rows, self.biases.columns);
+    }
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.learning_rate = learning_rate;
+    }
+    fn set_trainable(&mut self, trainable: bool) {
+        self.frozen = !trainable;
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn get_activation(&self) -> Option<Activations> {
+        Some(self.activation_fn)
+    }
+    fn set_activation(&mut self, activation: Activations) {
+        self.activation_fn = activation;
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.filters, self.kernel_size, self.channels)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        self.loss
+    }
+    fn parameter_count(&self) -> usize {
+        self.weights.rows * self.weights.columns + self.biases.rows * self.biases.columns
+    }
+    fn parameters(&self) -> Vec<f32> {
+        [self.weights.to_param(), self.biases.to_param()].concat()
+    }
+    fn parameters_finite(&self) -> bool {
+        self.weights.is_finite() && self.biases.is_finite()
+    }
+    fn set_parameters(&mut self, parameters: &[f32]) {
+        let split = self.weights.rows * self.weights.columns;
+        self.weights = Matrix::from_slice(&parameters[..split], self.weights.rows, self.weights.columns);
+        self.biases = Matrix::from_slice(&parameters[split..], self.biases.rows, self.biases.columns);
+    }
+    fn parameter_gradients(&self) -> Vec<f32> {
+        if self.weight_gradient.rows == 0 {
+            return vec![0.0; self.parameter_count()];
+        }
+        [self.weight_gradient.to_param(), self.bias_gradient.to_param()].concat()
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        let mut res = self.pre_activation.clone();
+        res.apply_inplace(&*self.activation_fn.get_function().derivative);
+        Box::new(res)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
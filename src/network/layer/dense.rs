@@ -1,4 +1,4 @@
-use crate::network::{matrix::Matrix, activations::{Activation, Activations}, input::Input};
+use crate::network::{matrix::Matrix, activations::Activations, input::Input, initializer::Initializer, optimizer::Optimizer};
 
 use super::layers::Layer;
 use serde::{Deserialize, Serialize};
@@ -6,15 +6,85 @@ use serde::{Deserialize, Serialize};
 ///A Dense Neural Network Layer of a model, containing just nodes, weights, biases and an
 ///activation function
 ///Implements the Layer trait
-#[derive(Serialize, Deserialize)]
+///
+///Uses the Adam optimization algorithm (or AdamW, see set_optimizer), keeping first and second
+///moment estimates of both the weights and biases, unless a momentum is set with set_momentum. Then it uses SGD with momentum,
+///keeping a velocity of both the weights and biases
+///
+///# Example
+///
+///Every layer takes the derivative of its own activation, so activations can be mixed freely
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+///
+///let inputs = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]];
+///let outputs = vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]];
+///
+///for hidden in [[Activations::RELU, Activations::SIGMOID], [Activations::SIGMOID, Activations::RELU]] {
+///    let mut net = Network::new_seeded(2, 3);
+///    net.add_layer(LayerTypes::DENSE(8, hidden[0], 0.01));
+///    net.add_layer(LayerTypes::DENSE(8, hidden[1], 0.01));
+///    net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.01));
+///    net.compile();
+///    net.fit_seeded(&inputs, &outputs, 1000, 3).unwrap();
+///
+///    for (input, output) in inputs.iter().zip(outputs.iter()) {
+///        assert!((net.predict(input.clone()).unwrap()[0] - output[0]).abs() < 0.1);
+///    }
+///}
+///```
+///
+///Adam reaches a far lower loss on XOR than plain SGD given the same epochs
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+///
+///let inputs = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]];
+///let outputs = vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]];
+///
+///let train = |sgd: bool| {
+///    let mut net = Network::new_seeded(2, 3);
+///    net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.05));
+///    net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.05));
+///    if sgd {
+///        net.set_momentum(0.0);
+///    }
+///    net.compile();
+///    net.fit_seeded(&inputs, &outputs, 300, 3).unwrap().loss[299]
+///};
+///
+///let (adam, sgd) = (train(false), train(true));
+///assert!(adam < 0.01);
+///assert!(adam < sgd / 10.0);
+///```
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Dense{
     pub weights: Matrix,   
     pub biases: Matrix,
     pub data: Matrix,
     loss: f32,
+    ///The values before the activation function, z = Wx + b, set on every forward pass. The
+    ///activation's derivative is taken of these rather than of the layer's output
+    #[serde(skip)]
+    pub pre_activation: Matrix,
 
     pub activation_fn: Activations,
     learning_rate: f32,
+    #[serde(default)]
+    initializer: Initializer,
+    #[serde(default)]
+    gradient_clip: Option<f32>,
+    #[serde(default)]
+    weight_decay: f32,
+    #[serde(default)]
+    frozen: bool,
+    #[serde(default)]
+    momentum: Option<f32>,
+    #[serde(default)]
+    no_bias: bool,
+    #[serde(default)]
+    optimizer: Optimizer,
 
     beta1: f32,
     beta2: f32,
@@ -24,41 +94,141 @@ pub struct Dense{
     m_weights: Matrix,
     v_weights: Matrix,
     m_biases: Matrix,
-    v_biases: Matrix
+    v_biases: Matrix,
+
+    #[serde(default)]
+    velocity_weights: Matrix,
+    #[serde(default)]
+    velocity_biases: Matrix,
+
+    #[serde(skip)]
+    weight_gradient: Matrix,
+    #[serde(skip)]
+    bias_gradient: Matrix
 }
 
 impl Dense{
-    pub fn new(layers: usize, layer_cols_before: usize, activation: Activations, learning_rate: f32) -> Dense{
+    ///Creates a Dense layer taking `inputs` values and producing `outputs` values, the weights are
+    ///an `outputs` x `inputs` Matrix and there is one bias per output
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{layer::{layers::Layer, dense::Dense}, activations::Activations, input::Input};
+    ///
+    ///let mut dense = Dense::new(2, 3, Activations::SIGMOID, 0.1);
+    ///assert_eq!((dense.weights.rows, dense.weights.columns), (3, 2));
+    ///assert_eq!((dense.biases.rows, dense.biases.columns), (3, 1));
+    ///
+    ///let res = dense.forward(&vec![1.0, 0.5]);
+    ///assert_eq!(res.to_param().len(), 3);
+    ///```
+    pub fn new(inputs: usize, outputs: usize, activation: Activations, learning_rate: f32) -> Dense{
+        Dense::new_with_init(inputs, outputs, activation, learning_rate, Initializer::Uniform)
+    }
+    ///Creates a Dense layer exactly like new, filling in its weights with `initializer`
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{layer::dense::Dense, activations::Activations, initializer::Initializer};
+    ///
+    ///let dense = Dense::new_with_init(2, 3, Activations::RELU, 0.1, Initializer::He);
+    ///assert_eq!((dense.weights.rows, dense.weights.columns), (3, 2));
+    ///assert!(dense.biases.data.iter().all(|bias| bias[0] == 0.0));
+    ///```
+    pub fn new_with_init(inputs: usize, outputs: usize, activation: Activations, learning_rate: f32, initializer: Initializer) -> Dense{
+        let weights = initializer.weights(outputs, inputs);
+        let biases = initializer.biases(weights.rows);
         let mut res = Dense { 
             loss: 1.0,
-            weights: Matrix::new_random(layer_cols_before, layers),
-            biases: Matrix::new_random(layer_cols_before, 1),
 
-            m_weights: Matrix::new_empty(layer_cols_before, layers),
-            v_weights: Matrix::new_empty(layer_cols_before, layers),
+            m_weights: Matrix::new_empty(weights.rows, weights.columns),
+            v_weights: Matrix::new_empty(weights.rows, weights.columns),
+
+            m_biases: Matrix::new_empty(biases.rows, 1),
+            v_biases: Matrix::new_empty(biases.rows, 1),
 
-            m_biases: Matrix::new_empty(layer_cols_before, 1),
-            v_biases: Matrix::new_empty(layer_cols_before, 1),
+            velocity_weights: Matrix::new_empty(weights.rows, weights.columns),
+            velocity_biases: Matrix::new_empty(biases.rows, 1),
 
+            weight_gradient: Matrix::new_empty(weights.rows, weights.columns),
+            bias_gradient: Matrix::new_empty(biases.rows, 1),
+
+            weights,
+            biases,
             data: Matrix::new_random(0, 0),
+            pre_activation: Matrix::new_empty(0, 0),
             activation_fn: activation,
             learning_rate,
+            initializer,
+            gradient_clip: None,
+            weight_decay: 0.0,
+            frozen: false,
+            momentum: None,
+            no_bias: false,
+            optimizer: Optimizer::Adam,
             beta1: 0.0,
             beta2: 0.0,
             epsilon: 0.0,
-            time: 1
+            time: 0
         };
         (res.beta1, res.beta2) = res.get_betas();
         res.epsilon = res.get_epsilon();
 
         res
     }
+    ///Creates a Dense layer exactly like new but without any biases, the output is just the
+    ///weighted sum of the inputs. Commonly used right before a BatchNorm layer, whose shift makes
+    ///a bias redundant
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{layer::{layers::Layer, dense::Dense}, activations::Activations, input::Input, matrix::Matrix};
+    ///
+    ///let mut dense = Dense::new_without_bias(2, 3, Activations::TANH, 0.1);
+    ///assert_eq!(dense.biases.rows, 0);
+    ///assert_eq!(dense.parameter_count(), 6);
+    ///assert_eq!(dense.forward(&vec![0.0, 0.0]).to_param(), vec![0.0; 3]);
+    ///
+    ///for i in 0..50 {
+    ///    let input = vec![(i % 3) as f32, (i % 5) as f32 / 5.0];
+    ///    let output = dense.forward(&input).to_param();
+    ///    let errors: Vec<f32> = output.iter().map(|value| 0.5 - value).collect();
+    ///    let errors: Box<dyn Input> = Box::new(Matrix::from(vec![errors]).transpose());
+    ///    dense.backward(dense.update_gradient(), errors, Box::new(Matrix::from(vec![input]).transpose()));
+    ///    dense.apply_gradients(1.0);
+    ///}
+    ///assert_eq!(dense.biases.rows, 0);
+    ///assert_eq!(dense.parameters().len(), 6);
+    ///```
+    pub fn new_without_bias(inputs: usize, outputs: usize, activation: Activations, learning_rate: f32) -> Dense{
+        let mut res = Dense::new(inputs, outputs, activation, learning_rate);
+        res.no_bias = true;
+        res.biases = Matrix::new_empty(0, 0);
+        res.m_biases = Matrix::new_empty(0, 0);
+        res.v_biases = Matrix::new_empty(0, 0);
+        res.velocity_biases = Matrix::new_empty(0, 0);
+        res.bias_gradient = Matrix::new_empty(0, 0);
+        res
+    }
     fn get_betas(&self) -> (f32, f32){
         (0.9, 0.999)
     }
     fn get_epsilon(&self) -> f32{
         1e-10
     }
+    ///Re-initializes the weights for a new input width, used when the width of the layer before
+    ///couldn't be known when the network was compiled
+    fn resize_inputs(&mut self, inputs: usize) {
+        let outputs = self.weights.rows;
+        self.weights = self.initializer.weights(outputs, inputs);
+        self.m_weights = Matrix::new_empty(outputs, inputs);
+        self.v_weights = Matrix::new_empty(outputs, inputs);
+        self.velocity_weights = Matrix::new_empty(outputs, inputs);
+        self.weight_gradient = Matrix::new_empty(outputs, inputs);
+    }
 }
 
 #[typetag::serde]
@@ -68,33 +238,103 @@ impl Layer for Dense{
     }
     ///Moves the DNN forward through the weights and biases of this current layer
     ///Maps an activation function and then returns the resultant Matrix
-    fn forward(&mut self, inputs: &Box<dyn Input>) -> Box<dyn Input> {
-        self.data = (self.weights.clone() * &Matrix::from(inputs.to_param_2d()).transpose() + &self.biases)
-            .map(self.activation_fn.get_function().function);
+    ///
+    ///Every row of the input is treated as its own sample, so a batch of inputs moves through the
+    ///layer in a single matrix multiplication with the bias broadcast across the batch
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let inputs_mat = Matrix::from(inputs.to_param_2d()).transpose();
+        if self.weights.columns == 0 {
+            self.resize_inputs(inputs_mat.rows);
+        }
+        self.pre_activation = self.weights.clone() * &inputs_mat;
+        if !self.no_bias {
+            self.pre_activation = self.pre_activation.add_broadcast(&self.biases);
+        }
+        self.data = self.activation_fn.apply(self.pre_activation.clone());
 
-        Box::new(self.data.clone().transpose())
+        Box::new(self.data.transpose())
     }
     ///Does Back Propegation according to simple Dense network rules
-    ///Finds the error of the previous layer and returns what the updated weights and biases should
-    ///be in that layer, updates the gradients and errors to move backwards once
+    ///Finds the error of the previous layer and accumulates the weight and bias gradients of this
+    ///layer, which are only applied once apply_gradients is called. Returns the errors to move
+    ///backwards once
+    ///
+    ///`gradients` is this layer's activation derivative (see update_gradient), which is applied to
+    ///the errors before they're passed back, so every layer only ever applies its own derivative
     fn backward(&mut self, gradients: Box<dyn Input>, errors: Box<dyn Input>, data: Box<dyn Input>) -> Box<dyn Input> {
         let mut gradients_mat = Matrix::from(gradients.to_param_2d());
-        let mut errors_mat = Matrix::from(errors.to_param_2d());
-        let data_mat = Matrix::from(data.to_param_2d());
+        let errors_mat = Matrix::from(errors.to_param_2d());
+        let data_t = Matrix::from(data.to_param_2d()).transpose();
 
-        gradients_mat = gradients_mat.dot_multiply(&errors_mat) * self.learning_rate;
-        errors_mat = self.weights.clone().transpose() * &errors_mat;
+        gradients_mat = gradients_mat.dot_multiply(&errors_mat);
+        let errors_mat = self.weights.transpose() * &gradients_mat;
 
         self.loss = 0.0;
         errors_mat.to_param().iter().for_each(|error| {
             self.loss += error.powi(2);
         });
 
-        self.loss = self.loss / errors_mat.to_param().len() as f32;
+        self.loss /= errors_mat.to_param().len() as f32;
 
-        self.time += 1;
+        let mut weight_gradient = gradients_mat.clone() * &data_t;
+        //Gradients point the way the weights should move, so the decay is subtracted
+        if self.weight_decay != 0.0 {
+            weight_gradient = weight_gradient - &(self.weights.clone() * self.weight_decay);
+        }
+        //Clipped after the errors are passed back, so earlier layers clip their own gradients
+        if let Some(max_norm) = self.gradient_clip {
+            let bias_norm = if self.no_bias { 0.0 } else { gradients_mat.l2_norm() };
+            let norm = (weight_gradient.l2_norm().powi(2) + bias_norm.powi(2)).sqrt();
+            if norm > max_norm {
+                weight_gradient = weight_gradient * (max_norm / norm);
+                gradients_mat = gradients_mat * (max_norm / norm);
+            }
+        }
+
+        //Gradients are skipped during serialization, so a freshly loaded layer starts out empty
+        if self.weight_gradient.rows == 0 {
+            self.weight_gradient = Matrix::new_empty(self.weights.rows, self.weights.columns);
+            self.bias_gradient = Matrix::new_empty(self.biases.rows, self.biases.columns);
+        }
+        if !self.frozen {
+            self.weight_gradient = self.weight_gradient.clone() + &weight_gradient;
+            if !self.no_bias {
+                self.bias_gradient = self.bias_gradient.clone() + &gradients_mat;
+            }
+        }
+
+        Box::new(errors_mat)
+    }
+    ///Updates the weights and biases from every gradient accumulated since the last update using
+    ///the Adam optimization algorithm (or SGD with momentum if one is set), scaling the
+    ///accumulated gradients by `scale` first (1 over the batch size averages them)
+    fn apply_gradients(&mut self, scale: f32) {
+        //The moment estimates would keep moving a frozen layer even without any new gradients
+        if self.weight_gradient.rows == 0 || self.frozen {
+            return;
+        }
+        let weight_gradient = self.weight_gradient.clone() * scale;
+        let gradients_mat = self.bias_gradient.clone() * scale;
+
+        if let Some(momentum) = self.momentum {
+            //A velocity loaded from a network saved before momentum existed starts out empty
+            if self.velocity_weights.rows != self.weights.rows || self.velocity_weights.columns != self.weights.columns {
+                self.velocity_weights = Matrix::new_empty(self.weights.rows, self.weights.columns);
+                self.velocity_biases = Matrix::new_empty(self.biases.rows, self.biases.columns);
+            }
+            //The gradients already point the way the weights should move, so they're added
+            self.velocity_weights = self.velocity_weights.clone() * momentum + &(weight_gradient * self.learning_rate);
+            self.velocity_biases = self.velocity_biases.clone() * momentum + &(gradients_mat * self.learning_rate);
 
-        let weight_gradient = gradients_mat.clone() * &(data_mat.clone().transpose());
+            self.weights = self.weights.clone() + &self.velocity_weights;
+            self.biases = self.biases.clone() + &self.velocity_biases;
+
+            self.weight_gradient = Matrix::new_empty(self.weights.rows, self.weights.columns);
+            self.bias_gradient = Matrix::new_empty(self.biases.rows, self.biases.columns);
+            return;
+        }
+
+        self.time += 1;
 
         self.m_weights = self.m_weights.clone() * self.beta1 + &(weight_gradient.clone() * (1.0 - self.beta1));
         self.v_weights = self.v_weights.clone() * self.beta2 + &((weight_gradient^2) * (1.0 - self.beta2));
@@ -108,17 +348,65 @@ impl Layer for Dense{
         let m_bias_hat = self.m_biases.clone() / (1.0 - self.beta1.powi(self.time as i32));
         let v_bias_hat = self.v_biases.clone() / (1.0 - self.beta2.powi(self.time as i32));
 
-        let weights_update = m_weights_hat.clone() / &(v_weights_hat.sqrt() + self.epsilon);
-        let bias_update = m_bias_hat.clone() / &(v_bias_hat.sqrt() + self.epsilon);
+        let weights_update = m_weights_hat.elementwise_div(&(v_weights_hat.elementwise_sqrt() + self.epsilon)) * self.learning_rate;
+        let bias_update = m_bias_hat.elementwise_div(&(v_bias_hat.elementwise_sqrt() + self.epsilon)) * self.learning_rate;
 
         self.biases = self.biases.clone() + &bias_update;
         self.weights = self.weights.clone() + &weights_update;
+        //Decoupled from the gradients, so the moment estimates never see the decay
+        if let Optimizer::AdamW { weight_decay } = self.optimizer {
+            self.weights = self.weights.clone() * (1.0 - self.learning_rate * weight_decay);
+        }
 
-        Box::new(errors_mat)
+        self.weight_gradient = Matrix::new_empty(self.weights.rows, self.weights.columns);
+        self.bias_gradient = Matrix::new_empty(self.biases.rows, self.biases.columns);
+    }
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.learning_rate = learning_rate;
+    }
+    fn set_trainable(&mut self, trainable: bool) {
+        self.frozen = !trainable;
+    }
+    fn set_gradient_clip(&mut self, max_norm: Option<f32>) {
+        self.gradient_clip = max_norm;
+    }
+    fn set_weight_decay(&mut self, lambda: f32) {
+        self.weight_decay = lambda;
+    }
+    fn set_momentum(&mut self, momentum: Option<f32>) {
+        self.momentum = momentum;
+    }
+    fn set_optimizer(&mut self, optimizer: Optimizer) {
+        self.optimizer = optimizer;
     }
 
+    fn parameter_count(&self) -> usize {
+        self.weights.rows * self.weights.columns + self.biases.rows * self.biases.columns
+    }
+    fn parameters(&self) -> Vec<f32> {
+        [self.weights.to_param(), self.biases.to_param()].concat()
+    }
+    fn parameters_finite(&self) -> bool {
+        self.weights.is_finite() && self.biases.is_finite()
+    }
+    fn set_parameters(&mut self, parameters: &[f32]) {
+        let split = self.weights.rows * self.weights.columns;
+        self.weights = Matrix::from_slice(&parameters[..split], self.weights.rows, self.weights.columns);
+        self.biases = Matrix::from_slice(&parameters[split..], self.biases.rows, self.biases.columns);
+    }
+    fn parameter_gradients(&self) -> Vec<f32> {
+        if self.weight_gradient.rows == 0 {
+            return vec![0.0; self.parameter_count()];
+        }
+        [self.weight_gradient.to_param(), self.bias_gradient.to_param()].concat()
+    }
     fn update_gradient(&self) -> Box<dyn Input> {
-        Box::new(self.data.clone().map(self.activation_fn.get_function().derivative))
+        let mut res = self.pre_activation.clone();
+        res.apply_inplace(&*self.activation_fn.get_function().derivative);
+        Box::new(res)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
     }
 
     /*fn backward(&mut self, inputs: &Matrix, gradients: &Matrix, errors: &Matrix, layer_prev: &Matrix, layer_prev_bias: &Matrix) -> (Matrix, Matrix, Matrix, Matrix){
@@ -136,13 +424,16 @@ impl Layer for Dense{
             self.loss += error.powi(2);
         });
 
-        self.loss = self.loss / errors_mat.to_param().len() as f32;
+        self.loss /= errors_mat.to_param().len() as f32;
 
         gradients_mat = self.data.map(self.activation_fn.get_function().derivative);
         (new_biases.clone(), new_layer_prev.clone(), gradients_mat, errors_mat)
     }*/
     fn get_activation(&self) -> Option<Activations> {
-        Some(self.activation_fn.clone())
+        Some(self.activation_fn)
+    }
+    fn set_activation(&mut self, activation: Activations) {
+        self.activation_fn = activation;
     }
     fn shape(&self) -> (usize, usize, usize){
         (self.weights.rows, self.weights.columns, 0)
@@ -0,0 +1,111 @@
+use rand::{rngs::StdRng, thread_rng, Rng, RngCore, SeedableRng};
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, input::Input, modes::Mode, rng};
+
+use super::layers::Layer;
+
+///A Dropout layer, while training it zeroes each activation with a probability of `rate` and
+///scales the survivors by 1 / (1 - rate). Outside of training it passes its input through
+///unchanged
+///Implements the Layer trait
+///
+///The masks come from an RNG the layer seeds when it's created, drawn from rng::with_seed when
+///inside of it (as every layer of a Network::new_seeded network is), so the same seed always
+///drops the same activations. A loaded layer doesn't keep that RNG and falls back to the thread's
+///
+///# Example
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+///
+///let inputs = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]];
+///let outputs = vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]];
+///
+///let train = || {
+///    let mut net = Network::new_seeded(2, 11);
+///    net.add_layer(LayerTypes::DENSE(8, Activations::RELU, 0.05));
+///    net.add_layer(LayerTypes::DROPOUT(0.5));
+///    net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.05));
+///    net.compile();
+///    net.fit_seeded(&inputs, &outputs, 50, 11).unwrap();
+///    net.layers.iter().map(|layer| layer.parameters()).collect::<Vec<Vec<f32>>>()
+///};
+///
+///assert_eq!(train(), train());
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Dropout{
+    rate: f32,
+    mode: Mode,
+    pub data: Matrix,
+    mask: Matrix,
+    #[serde(skip)]
+    rng: Option<StdRng>,
+}
+
+impl Dropout{
+    pub fn new(rate: f32) -> Dropout {
+        Dropout {
+            rate: rate.clamp(0.0, 1.0),
+            mode: Mode::TRAIN,
+            data: Matrix::new_empty(0, 0),
+            mask: Matrix::new_empty(0, 0),
+            rng: Some(StdRng::seed_from_u64(rng::with_rng(|rng| rng.next_u64()))),
+        }
+    }
+}
+
+#[typetag::serde]
+impl Layer for Dropout{
+    ///Generates a new dropout mask and applies it to the inputs when training, otherwise acts as
+    ///the identity
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = Matrix::from(inputs.to_param_2d()).transpose();
+
+        self.mask = Matrix::new_empty(input.rows, input.columns) + 1.0;
+        if self.mode == Mode::TRAIN && self.rate > 0.0 {
+            let mut thread = thread_rng();
+            let rng: &mut dyn RngCore = match self.rng.as_mut() {
+                Some(rng) => rng,
+                None => &mut thread,
+            };
+            let scale = if self.rate < 1.0 { 1.0 / (1.0 - self.rate) } else { 0.0 };
+            for row in self.mask.data.iter_mut() {
+                for value in row.iter_mut() {
+                    *value = if rng.gen::<f32>() < self.rate { 0.0 } else { scale };
+                }
+            }
+        }
+
+        self.data = self.mask.clone().dot_multiply(&input);
+        Box::new(self.data.transpose())
+    }
+    ///Masks the errors with the same mask used during the last forward pass, so dropped
+    ///activations pass no error backwards
+    fn backward(&mut self, _gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let errors_mat = Matrix::from(errors.to_param_2d());
+        Box::new(self.mask.clone().dot_multiply(&errors_mat))
+    }
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.data.rows, self.data.columns, 0)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        0.0
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        Box::new(Matrix::new_empty(self.data.rows, self.data.columns) + 1.0)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
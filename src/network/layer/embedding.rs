@@ -0,0 +1,183 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, input::Input, error::NetworkError};
+
+use super::layers::Layer;
+
+///The learning rate the embeddings of an EMBEDDING layer train with
+pub const DEFAULT_EMBEDDING_LEARNING_RATE: f32 = 0.05;
+
+///An Embedding layer, maps integer tokens to learnable vectors by looking up one row of its
+///`vocab_size` x `embed_dim` embedding matrix per token
+///Implements the Layer trait
+///
+///The input is a sequence of token indices, either as a Tokens input or as whole numbered f32
+///values (so datasets of Vec<f32> can be used with fit). The embeddings of every token are laid
+///one after the other, so a sequence of n tokens produces n * `embed_dim` values for the layers
+///that follow. A network returns NetworkError::InvalidToken for a token outside of the vocabulary
+///or one that isn't a whole number, the layer on its own panics
+///
+///Only the rows of the tokens that were looked up receive gradients during back propegation
+///
+///# Example
+///
+///Tokens 0 and 1 mean the same thing, tokens 2 and 3 the opposite. Training pulls the
+///embeddings of 0 and 1 together
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, input::Tokens, error::NetworkError};
+///
+///let mut new_net = Network::new(1);
+///new_net.add_layer(LayerTypes::EMBEDDING(4, 3));
+///new_net.add_layer(LayerTypes::DENSE(1, Activations::LEAKYRELU(1.0), 0.01));
+///new_net.compile();
+///
+///let distance = |net: &Network| {
+///    let embeddings = net.layers[0].parameters();
+///    (0..3).map(|d| (embeddings[d] - embeddings[3 + d]).powi(2)).sum::<f32>().sqrt()
+///};
+///let before = distance(&new_net);
+///
+///let inputs: Vec<Vec<f32>> = vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0]];
+///let outputs: Vec<Vec<f32>> = vec![vec![1.0], vec![1.0], vec![-1.0], vec![-1.0]];
+///new_net.fit_batched(&inputs, &outputs, 300, 1).unwrap();
+///
+///assert!(distance(&new_net) < before);
+///assert_eq!(new_net.predict_matrix(vec![0.0]).unwrap().rows, 1);
+///assert_eq!(new_net.layers[0].forward(&Tokens(vec![1, 2])).to_param().len(), 6);
+///
+///assert_eq!(new_net.predict(vec![4.0]), Err(NetworkError::InvalidToken { token: 4.0, vocab_size: 4 }));
+///assert_eq!(new_net.predict(vec![-1.0]), Err(NetworkError::InvalidToken { token: -1.0, vocab_size: 4 }));
+///assert!(new_net.fit(&[vec![1.5]], &[vec![1.0]], 1).is_err());
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Embedding{
+    //One row per token of the vocabulary
+    pub embeddings: Matrix,
+    pub data: Matrix,
+    learning_rate: f32,
+    loss: f32,
+    #[serde(default)]
+    frozen: bool,
+
+    #[serde(skip)]
+    tokens: Vec<usize>,
+    #[serde(skip)]
+    gradient: Matrix,
+}
+
+impl Embedding{
+    pub fn new(vocab_size: usize, embed_dim: usize, learning_rate: f32) -> Embedding {
+        Embedding {
+            embeddings: Matrix::new_random(vocab_size, embed_dim),
+            data: Matrix::new_empty(0, 0),
+            learning_rate,
+            loss: 1.0,
+            frozen: false,
+            tokens: vec![],
+            gradient: Matrix::new_empty(vocab_size, embed_dim),
+        }
+    }
+}
+
+fn is_token(token: f32, vocab_size: usize) -> bool {
+    token >= 0.0 && token.fract() == 0.0 && (token as usize) < vocab_size
+}
+
+#[typetag::serde]
+impl Layer for Embedding{
+    ///Looks up the embedding of every token, returning them one after the other
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let vocab_size = self.embeddings.rows;
+        self.tokens = inputs.to_param().iter().map(|&token| {
+            if !is_token(token, vocab_size) {
+                panic!("Error attempting to embed token {} with a vocabulary of {} tokens", token, vocab_size);
+            }
+            token as usize
+        }).collect();
+
+        let values: Vec<f32> = self.tokens.iter().flat_map(|&token| self.embeddings.row(token).to_vec()).collect();
+        self.data = Matrix::from_slice(&values, values.len(), 1);
+
+        Box::new(self.data.transpose())
+    }
+    ///Accumulates the errors of every embedding into the row of the token it came from. Tokens
+    ///can't be moved by errors, so the errors returned are all 0
+    fn backward(&mut self, _gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let errors_flat = errors.to_param();
+        let embed_dim = self.embeddings.columns;
+
+        if self.gradient.rows == 0 {
+            self.gradient = Matrix::new_empty(self.embeddings.rows, embed_dim);
+        }
+        if !self.frozen {
+            for (position, &token) in self.tokens.iter().enumerate() {
+                for d in 0..embed_dim {
+                    self.gradient.data[token][d] += errors_flat[position * embed_dim + d];
+                }
+            }
+        }
+
+        self.loss = errors_flat.iter().map(|error| error.powi(2)).sum::<f32>() / errors_flat.len().max(1) as f32;
+        Box::new(Matrix::new_empty(self.tokens.len(), 1))
+    }
+    ///Moves the embeddings along the accumulated gradients, scaled by `scale` and the learning
+    ///rate
+    fn apply_gradients(&mut self, scale: f32) {
+        if self.gradient.rows == 0 {
+            return;
+        }
+        self.embeddings = self.embeddings.clone() + &(self.gradient.clone() * (scale * self.learning_rate));
+        self.gradient = Matrix::new_empty(self.embeddings.rows, self.embeddings.columns);
+    }
+    ///Every token has to be a whole number below the vocabulary size
+    fn check_input(&self, inputs: &dyn Input) -> Result<(), NetworkError> {
+        let vocab_size = self.embeddings.rows;
+        match inputs.to_param().into_iter().find(|&token| !is_token(token, vocab_size)) {
+            Some(token) => Err(NetworkError::InvalidToken { token, vocab_size }),
+            None => Ok(()),
+        }
+    }
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.learning_rate = learning_rate;
+    }
+    fn set_trainable(&mut self, trainable: bool) {
+        self.frozen = !trainable;
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.embeddings.rows, self.embeddings.columns, 0)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        self.loss
+    }
+    fn parameter_count(&self) -> usize {
+        self.embeddings.rows * self.embeddings.columns
+    }
+    fn parameters(&self) -> Vec<f32> {
+        self.embeddings.to_param()
+    }
+    fn parameters_finite(&self) -> bool {
+        self.embeddings.is_finite()
+    }
+    fn set_parameters(&mut self, parameters: &[f32]) {
+        self.embeddings = Matrix::from_slice(parameters, self.embeddings.rows, self.embeddings.columns);
+    }
+    fn parameter_gradients(&self) -> Vec<f32> {
+        if self.gradient.rows == 0 {
+            return vec![0.0; self.parameter_count()];
+        }
+        self.gradient.to_param()
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        Box::new(Matrix::new_empty(self.data.rows, self.data.columns) + 1.0)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, input::Input};
+
+use super::layers::Layer;
+
+///A Flatten layer, reshapes a multi-dimensional feature map into a single vector so it can feed
+///into Dense layers, and reshapes errors back into the original feature map during back
+///propegation
+///Implements the Layer trait
+///
+///# Example
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, flatten::Flatten}, matrix::Matrix, input::Input};
+///
+///let mut flatten = Flatten::new();
+///let input = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+///
+///let res = flatten.forward(&input);
+///assert_eq!(res.to_param(), vec![1.0, 2.0, 3.0, 4.0]);
+///
+///let errors = Matrix::from(vec![vec![0.1], vec![0.2], vec![0.3], vec![0.4]]);
+///let reshaped = flatten.backward(flatten.update_gradient(), Box::new(errors), Box::new(input));
+///assert_eq!(reshaped.to_param_2d(), vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+///```
+#[derive(Clone, Serialize, Deserialize, Default)]
+pub struct Flatten{
+    input_shape: (usize, usize),
+    pub data: Matrix,
+}
+
+impl Flatten{
+    pub fn new() -> Flatten {
+        Flatten {
+            input_shape: (0, 0),
+            data: Matrix::new_empty(0, 0),
+        }
+    }
+}
+
+#[typetag::serde]
+impl Layer for Flatten{
+    ///Flattens the input row by row, remembering its shape for back propegation
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = inputs.to_param_2d();
+        self.input_shape = (input.len(), input.first().map_or(0, |row| row.len()));
+
+        let flat = inputs.to_param();
+        self.data = Matrix::from(flat.to_param_2d()).transpose();
+
+        Box::new(flat)
+    }
+    ///Reshapes the incoming errors back to the shape of the last input
+    fn backward(&mut self, _gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let flat = errors.to_param();
+        let mut res = Matrix::new_empty(self.input_shape.0, self.input_shape.1);
+
+        for (idx, error) in flat.iter().enumerate().take(self.input_shape.0 * self.input_shape.1) {
+            res.data[idx / self.input_shape.1][idx % self.input_shape.1] = *error;
+        }
+
+        Box::new(res)
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.data.rows, self.input_shape.0 * self.input_shape.1, 0)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        0.0
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        Box::new(Matrix::new_empty(self.data.rows, self.data.columns) + 1.0)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
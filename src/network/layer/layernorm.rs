@@ -0,0 +1,208 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, input::Input};
+
+use super::layers::Layer;
+
+const EPSILON: f32 = 1e-5;
+///The learning rate the scale and shift of a LAYERNORM layer train with
+pub const DEFAULT_LAYERNORM_LEARNING_RATE: f32 = 0.01;
+
+///A Layer Normalization layer, normalizes the features of every sample to a mean of 0 and a
+///variance of 1 and then applies a learnable scale (gamma) and shift (beta) per feature
+///Implements the Layer trait
+///
+///Unlike BatchNorm the statistics come from each sample on its own, so the layer keeps no running
+///statistics, behaves the same while training and evaluating and works with batches of any size,
+///a single sample included
+///
+///# Example
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, layernorm::LayerNorm}, input::Input};
+///
+///let mut norm = LayerNorm::new(4, 0.01);
+///let batch: Vec<Vec<f32>> = vec![vec![1.0, 2.0, 3.0, 4.0], vec![-50.0, 10.0, 0.0, 1000.0]];
+///
+///let res = norm.forward(&batch).to_param_2d();
+///for sample in res.iter() {
+///    let mean = sample.iter().sum::<f32>() / sample.len() as f32;
+///    let variance = sample.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / sample.len() as f32;
+///    assert!(mean.abs() < 1e-4);
+///    assert!((variance - 1.0).abs() < 1e-3);
+///}
+///
+/////Every sample is normalized on its own, whatever else is in the batch
+///let alone = norm.forward(&vec![1.0, 2.0, 3.0, 4.0]).to_param();
+///assert_eq!(alone, res[0]);
+///```
+///
+///Backward agrees with the numerical gradients of forward, for the scale and shift as well as the
+///input
+///
+///```
+///use triton_grow::network::{gradcheck::{check_layer, check_input}, layer::{layers::Layer, layernorm::LayerNorm}};
+///
+///let mut norm = LayerNorm::new(3, 0.01);
+///norm.set_parameters(&[0.5, -1.5, 2.0, 0.3, 0.1, -0.7]);
+///let input = vec![0.5, -0.3, 0.8];
+///assert!(check_layer(&mut norm, &input, 1e-2) < 1e-2);
+///assert!(check_input(&mut norm, &input, 1e-2) < 1e-2);
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LayerNorm{
+    pub gamma: Matrix,
+    pub beta: Matrix,
+    pub data: Matrix,
+    learning_rate: f32,
+    loss: f32,
+    #[serde(default)]
+    frozen: bool,
+
+    #[serde(skip)]
+    normalized: Matrix,
+    #[serde(skip)]
+    std_devs: Vec<f32>,
+    #[serde(skip)]
+    gamma_gradient: Matrix,
+    #[serde(skip)]
+    beta_gradient: Matrix,
+}
+
+impl LayerNorm{
+    pub fn new(features: usize, learning_rate: f32) -> LayerNorm {
+        LayerNorm {
+            gamma: Matrix::new_empty(features, 1) + 1.0,
+            beta: Matrix::new_empty(features, 1),
+            data: Matrix::new_empty(0, 0),
+            learning_rate,
+            loss: 0.0,
+            frozen: false,
+            normalized: Matrix::new_empty(0, 0),
+            std_devs: vec![],
+            gamma_gradient: Matrix::new_empty(features, 1),
+            beta_gradient: Matrix::new_empty(features, 1),
+        }
+    }
+}
+
+#[typetag::serde]
+impl Layer for LayerNorm{
+    ///Normalizes the features of every sample (column) by that sample's own mean and variance,
+    ///then scales and shifts every feature
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = Matrix::from(inputs.to_param_2d()).transpose();
+        //The width of the layer before can be unknown until data flows through the network
+        if self.gamma.rows != input.rows {
+            *self = LayerNorm { frozen: self.frozen, ..LayerNorm::new(input.rows, self.learning_rate) };
+        }
+        let features = input.rows as f32;
+
+        self.normalized = Matrix::new_empty(input.rows, input.columns);
+        self.data = Matrix::new_empty(input.rows, input.columns);
+        self.std_devs = vec![0.0; input.columns];
+        for sample in 0..input.columns {
+            let mean = (0..input.rows).map(|feature| input.data[feature][sample]).sum::<f32>() / features;
+            let variance = (0..input.rows).map(|feature| (input.data[feature][sample] - mean).powi(2)).sum::<f32>() / features;
+            let std_dev = (variance + EPSILON).sqrt();
+            for feature in 0..input.rows {
+                let normalized = (input.data[feature][sample] - mean) / std_dev;
+                self.normalized.data[feature][sample] = normalized;
+                self.data.data[feature][sample] = self.gamma.data[feature][0] * normalized + self.beta.data[feature][0];
+            }
+            self.std_devs[sample] = std_dev;
+        }
+
+        Box::new(self.data.transpose())
+    }
+    ///Accumulates the gradients of gamma and beta and returns the errors of the input, which
+    ///account for every feature of a sample sharing the same mean and variance
+    fn backward(&mut self, _gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let errors_mat = Matrix::from(errors.to_param_2d());
+        let (features, samples) = (self.normalized.rows, self.normalized.columns);
+
+        //Gradients are skipped during serialization, so a freshly loaded layer starts out empty
+        if self.gamma_gradient.rows == 0 {
+            self.gamma_gradient = Matrix::new_empty(features, 1);
+            self.beta_gradient = Matrix::new_empty(features, 1);
+        }
+
+        let mut input_errors = Matrix::new_empty(features, samples);
+        for sample in 0..samples {
+            //The errors of the normalized values, before gamma scaled them
+            let scaled: Vec<f32> = (0..features).map(|feature| errors_mat.data[feature][sample] * self.gamma.data[feature][0]).collect();
+            let scaled_mean = scaled.iter().sum::<f32>() / features as f32;
+            let projected_mean = scaled.iter().enumerate().map(|(feature, error)| error * self.normalized.data[feature][sample]).sum::<f32>() / features as f32;
+
+            for (feature, scaled_error) in scaled.iter().enumerate() {
+                let normalized = self.normalized.data[feature][sample];
+                if !self.frozen {
+                    self.beta_gradient.data[feature][0] += errors_mat.data[feature][sample];
+                    self.gamma_gradient.data[feature][0] += errors_mat.data[feature][sample] * normalized;
+                }
+                input_errors.data[feature][sample] = (scaled_error - scaled_mean - normalized * projected_mean) / self.std_devs[sample];
+            }
+        }
+
+        let values = input_errors.to_param();
+        self.loss = values.iter().map(|error| error.powi(2)).sum::<f32>() / values.len().max(1) as f32;
+
+        Box::new(input_errors)
+    }
+    ///Moves gamma and beta along the accumulated gradients, scaled by `scale` and the learning
+    ///rate
+    fn apply_gradients(&mut self, scale: f32) {
+        if self.gamma_gradient.rows == 0 {
+            return;
+        }
+        self.gamma = self.gamma.clone() + &(self.gamma_gradient.clone() * (scale * self.learning_rate));
+        self.beta = self.beta.clone() + &(self.beta_gradient.clone() * (scale * self.learning_rate));
+
+        self.gamma_gradient = Matrix::new_empty(self.gamma.rows, 1);
+        self.beta_gradient = Matrix::new_empty(self.beta.rows, 1);
+    }
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.learning_rate = learning_rate;
+    }
+    fn set_trainable(&mut self, trainable: bool) {
+        self.frozen = !trainable;
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.gamma.rows, self.gamma.rows, 0)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        self.loss
+    }
+    fn parameter_count(&self) -> usize {
+        self.gamma.rows + self.beta.rows
+    }
+    fn parameters(&self) -> Vec<f32> {
+        [self.gamma.to_param(), self.beta.to_param()].concat()
+    }
+    fn parameters_finite(&self) -> bool {
+        self.gamma.is_finite() && self.beta.is_finite()
+    }
+    fn set_parameters(&mut self, parameters: &[f32]) {
+        let split = self.gamma.rows;
+        self.gamma = Matrix::from_slice(&parameters[..split], split, 1);
+        self.beta = Matrix::from_slice(&parameters[split..], self.beta.rows, 1);
+    }
+    fn parameter_gradients(&self) -> Vec<f32> {
+        if self.gamma_gradient.rows == 0 {
+            return vec![0.0; self.parameter_count()];
+        }
+        [self.gamma_gradient.to_param(), self.beta_gradient.to_param()].concat()
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        Box::new(Matrix::new_empty(self.data.rows, self.data.columns) + 1.0)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
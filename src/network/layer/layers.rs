@@ -1,36 +1,140 @@
-use crate::network::{input::Input, matrix::Matrix, activations::Activations, network::Network};
+use crate::network::{input::Input, matrix::Matrix, activations::Activations, modes::Mode, initializer::Initializer, optimizer::Optimizer, error::NetworkError};
 use serde::{Serialize, Deserialize};
 
-use super::dense::Dense;
+use super::{dense::Dense, pool::MaxPool, flatten::Flatten, dropout::Dropout, conv1d::Conv1D, rnn::Rnn, batchnorm::{BatchNorm, DEFAULT_BATCHNORM_LEARNING_RATE}, layernorm::{LayerNorm, DEFAULT_LAYERNORM_LEARNING_RATE}, residual::Residual, embedding::{Embedding, DEFAULT_EMBEDDING_LEARNING_RATE}, softmax::SoftmaxCE};
 
+///Layers are Send and Sync so a network can hand copies of them to other threads, see
+///Network::predict_parallel
 #[typetag::serde]
-pub trait Layer{
-    fn forward(&mut self, inputs: &Box<dyn Input>) -> Box<dyn Input> {
+pub trait Layer: Send + Sync {
+    fn forward(&mut self, _inputs: &dyn Input) -> Box<dyn Input> {
         Box::new(Matrix::new_random(0,0))
     }
     fn backward(&mut self, parsed: Box<dyn Input>, errors: Box<dyn Input>, data: Box<dyn Input>) -> Box<dyn Input>; 
+    ///Applies every gradient accumulated by backward since the last call, scaled by `scale`.
+    ///Layers without any trainable parameters can leave this as a no-op
+    fn apply_gradients(&mut self, _scale: f32) {}
+    ///Tells the layer whether it is being trained or used for inference
+    fn set_mode(&mut self, _mode: Mode) {}
+    ///Changes the rate the layer's parameters are updated at, layers without any trainable
+    ///parameters can leave this as a no-op
+    fn set_learning_rate(&mut self, _learning_rate: f32) {}
+    ///Caps the L2 norm of the weight and bias gradients the layer accumulates for every sample
+    ///during back propegation, None turns clipping off
+    fn set_gradient_clip(&mut self, _max_norm: Option<f32>) {}
+    ///Sets the L2 regularization strength, pulling the layer's weights towards 0 by `lambda`
+    ///times their value on every backward pass
+    fn set_weight_decay(&mut self, _lambda: f32) {}
+    ///Switches the layer from its own optimizer to SGD with momentum, None switches back
+    fn set_momentum(&mut self, _momentum: Option<f32>) {}
+    ///Changes the adaptive optimizer the layer updates its parameters with, layers that don't use
+    ///one can leave this as a no-op
+    fn set_optimizer(&mut self, _optimizer: Optimizer) {}
+    ///Freezes (false) or unfreezes (true) the layer's parameters, a frozen layer still passes
+    ///errors back to the layers before it but never updates its own weights and biases
+    fn set_trainable(&mut self, _trainable: bool) {}
+    ///Clears any state the layer carries from one input to the next, such as the hidden state of
+    ///a recurrent layer
+    fn reset_state(&mut self) {}
     fn get_data(&self) -> Box<dyn Input>;
     fn get_activation(&self) -> Option<Activations> {
         None
     }
+    ///Replaces the layer's activation function, layers without one can leave this as a no-op
+    fn set_activation(&mut self, _activation: Activations) {}
     fn shape(&self) -> (usize,usize,usize);
+    ///The width of the input this layer expects, or None if it can take inputs of any size
+    fn input_size(&self) -> Option<usize> {
+        Some(self.shape().1)
+    }
+    ///Checks the values of an input before it's fed forward, for layers where not every value
+    ///is valid. Its width is already checked against input_size
+    fn check_input(&self, _inputs: &dyn Input) -> Result<(), NetworkError> {
+        Ok(())
+    }
     fn get_loss(&self) -> f32;
+    ///How many trainable values (weights and biases) the layer holds
+    fn parameter_count(&self) -> usize {
+        0
+    }
+    ///Every trainable value of the layer flattened into one Vec, layers without any return an
+    ///empty Vec
+    fn parameters(&self) -> Vec<f32> {
+        vec![]
+    }
+    ///Whether every trainable value of the layer is finite (neither NaN nor infinite)
+    fn parameters_finite(&self) -> bool {
+        self.parameters().iter().all(|value| value.is_finite())
+    }
+    ///Replaces every trainable value of the layer, in the same order parameters returns them
+    fn set_parameters(&mut self, _parameters: &[f32]) {}
+    ///The gradients accumulated since the last update, in the same order as parameters. Like the
+    ///errors passed to backward these point in the direction that lowers the loss
+    fn parameter_gradients(&self) -> Vec<f32> {
+        vec![]
+    }
     fn update_gradient(&self) -> Box<dyn Input>;
+    ///A deep copy of the layer, with everything it has learned and its optimizer state
+    fn clone_box(&self) -> Box<dyn Layer>;
+}
+
+impl Clone for Box<dyn Layer> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub enum LayerTypes{
     //DENSE: Nodes, Activation Function, Learning Rate
     DENSE(usize, Activations, f32),
+    //DENSEINIT: Nodes, Activation Function, Learning Rate, Weight Initializer
+    DENSEINIT(usize, Activations, f32, Initializer),
+    //DENSENOBIAS: Nodes, Activation Function, Learning Rate, a Dense layer without any biases
+    DENSENOBIAS(usize, Activations, f32),
+    //MAXPOOL: Kernel Size, stride
+    MAXPOOL((usize, usize), usize),
+    //FLATTEN: Reshapes a feature map into a vector for the dense layers that follow
+    FLATTEN,
+    //DROPOUT: Rate
+    DROPOUT(f32),
+    //CONV1D: Filters, Kernel Size, Stride, Activation Function, Learning Rate
+    CONV1D(usize, usize, usize, Activations, f32),
+    //RNN: Hidden Size, Activation Function, Learning Rate
+    RNN(usize, Activations, f32),
+    //BATCHNORM: Normalizes every feature of the layer before it across the batch
+    BATCHNORM,
+    //LAYERNORM: Normalizes the features of every sample on its own
+    LAYERNORM,
+    //RESIDUAL: The layer to wrap, its input is added onto its output
+    RESIDUAL(Box<LayerTypes>),
+    //EMBEDDING: Vocabulary Size, Embedding Dimension
+    EMBEDDING(usize, usize),
+    //SOFTMAXCE: A stable Softmax output layer for Cross-Entropy, fed the logits of the layer before it
+    SOFTMAXCE,
     //NETWORK(Vec<LayerTypes>, usize),
     //CONV: Kernel Size, stride, Learning Rate
     //CONV((usize, usize), usize, f32),    
 }
 
 impl LayerTypes{
-    pub fn to_layer(&self, prev_cols: usize) -> Box<dyn Layer> {
-        return match self {
-            LayerTypes::DENSE(rows, activation, learning) => Box::new(Dense::new(rows.clone(), prev_cols, activation.clone(), learning.clone())),
+    ///Constructs the layer, `prev_size` is the width of the output of the layer before it (0 when
+    ///that isn't known until data flows through the network)
+    pub fn to_layer(&self, prev_size: usize) -> Box<dyn Layer> {
+        match self {
+            LayerTypes::DENSE(rows, activation, learning) => Box::new(Dense::new(prev_size, *rows, *activation, *learning)),
+            LayerTypes::DENSEINIT(rows, activation, learning, initializer) => Box::new(Dense::new_with_init(prev_size, *rows, *activation, *learning, *initializer)),
+            LayerTypes::DENSENOBIAS(rows, activation, learning) => Box::new(Dense::new_without_bias(prev_size, *rows, *activation, *learning)),
+            LayerTypes::MAXPOOL(kernel_size, stride) => Box::new(MaxPool::new(*kernel_size, *stride)),
+            LayerTypes::FLATTEN => Box::new(Flatten::new()),
+            LayerTypes::DROPOUT(rate) => Box::new(Dropout::new(*rate)),
+            LayerTypes::CONV1D(filters, kernel_size, stride, activation, learning) => Box::new(Conv1D::new(*filters, *kernel_size, *stride, *activation, *learning)),
+            LayerTypes::RNN(hidden_size, activation, learning) => Box::new(Rnn::new(prev_size, *hidden_size, *activation, *learning)),
+            LayerTypes::BATCHNORM => Box::new(BatchNorm::new(prev_size, DEFAULT_BATCHNORM_LEARNING_RATE)),
+            LayerTypes::LAYERNORM => Box::new(LayerNorm::new(prev_size, DEFAULT_LAYERNORM_LEARNING_RATE)),
+            LayerTypes::RESIDUAL(inner) => Box::new(Residual::new(inner.to_layer(prev_size))),
+            LayerTypes::EMBEDDING(vocab_size, embed_dim) => Box::new(Embedding::new(*vocab_size, *embed_dim, DEFAULT_EMBEDDING_LEARNING_RATE)),
+            LayerTypes::SOFTMAXCE => Box::new(SoftmaxCE::new()),
             /*LayerTypes::NETWORK(layers, batch_size) => {
                 let mut new_net: Network = Network::new(*batch_size);
                 layers.iter().for_each(|layer| {
@@ -40,12 +144,38 @@ impl LayerTypes{
                 Box::new(new_net)
             },*/
             //LayerTypes::CONV(shape, stride, learning) => Box::new()
-        };
+        }
+    }
+    ///The width of this layer's output given the width of its input, 0 when it can only be known
+    ///once data flows through the network
+    pub fn output_size(&self, input_size: usize) -> usize{
+        match self{
+            LayerTypes::DENSE(rows, _, _) | LayerTypes::DENSEINIT(rows, _, _, _) | LayerTypes::DENSENOBIAS(rows, _, _) | LayerTypes::RNN(rows, _, _) => *rows,
+            LayerTypes::DROPOUT(_) | LayerTypes::BATCHNORM | LayerTypes::LAYERNORM | LayerTypes::SOFTMAXCE => input_size,
+            LayerTypes::MAXPOOL(_, _) | LayerTypes::FLATTEN | LayerTypes::CONV1D(_, _, _, _, _) | LayerTypes::EMBEDDING(_, _) => 0,
+            LayerTypes::RESIDUAL(inner) => inner.output_size(input_size),
+        }
+    }
+    ///The learning rate the layer was declared with, None if it has nothing to train
+    pub fn learning_rate(&self) -> Option<f32>{
+        match self{
+            LayerTypes::DENSE(_, _, learning) | LayerTypes::DENSEINIT(_, _, learning, _) | LayerTypes::DENSENOBIAS(_, _, learning) | LayerTypes::CONV1D(_, _, _, _, learning) | LayerTypes::RNN(_, _, learning) => Some(*learning),
+            LayerTypes::BATCHNORM => Some(DEFAULT_BATCHNORM_LEARNING_RATE),
+            LayerTypes::LAYERNORM => Some(DEFAULT_LAYERNORM_LEARNING_RATE),
+            LayerTypes::EMBEDDING(_, _) => Some(DEFAULT_EMBEDDING_LEARNING_RATE),
+            LayerTypes::MAXPOOL(_, _) | LayerTypes::FLATTEN | LayerTypes::DROPOUT(_) | LayerTypes::SOFTMAXCE => None,
+            LayerTypes::RESIDUAL(inner) => inner.learning_rate(),
+        }
     }
     pub fn get_size(&self) -> usize{
-        return match self{
-            LayerTypes::DENSE(rows, _, _) => *rows,
-            _ => 0
+        match self{
+            LayerTypes::DENSE(rows, _, _) | LayerTypes::DENSEINIT(rows, _, _, _) | LayerTypes::DENSENOBIAS(rows, _, _) | LayerTypes::RNN(rows, _, _) => *rows,
+            LayerTypes::CONV1D(filters, _, _, _, _) => *filters,
+            LayerTypes::EMBEDDING(_, embed_dim) => *embed_dim,
+            //Pooling, flattening, dropout, normalization and softmax keep no weights of their own
+            //size, their output size depends entirely on their input
+            LayerTypes::MAXPOOL(_, _) | LayerTypes::FLATTEN | LayerTypes::DROPOUT(_) | LayerTypes::BATCHNORM | LayerTypes::LAYERNORM | LayerTypes::SOFTMAXCE => 0,
+            LayerTypes::RESIDUAL(inner) => inner.get_size(),
         }
     }
 }
@@ -1,4 +1,26 @@
+//typetag's registry expands to impls nested inside a const, which newer compilers flag
+#[allow(non_local_definitions)]
 pub mod dense;
 pub mod conv;
+#[allow(non_local_definitions)]
+pub mod conv1d;
+#[allow(non_local_definitions)]
+pub mod pool;
+#[allow(non_local_definitions)]
+pub mod flatten;
+#[allow(non_local_definitions)]
+pub mod dropout;
+#[allow(non_local_definitions)]
+pub mod rnn;
+#[allow(non_local_definitions)]
+pub mod batchnorm;
+#[allow(non_local_definitions)]
+pub mod layernorm;
+#[allow(non_local_definitions)]
+pub mod residual;
+#[allow(non_local_definitions)]
+pub mod embedding;
+#[allow(non_local_definitions)]
+pub mod softmax;
+#[allow(non_local_definitions)]
 pub mod layers;
-
@@ -0,0 +1,126 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, input::Input};
+
+use super::layers::Layer;
+
+///A Max Pooling layer, downsamples a 2D feature map by only keeping the largest value of every
+///kernel sized window it slides over
+///Implements the Layer trait
+///
+///# Example
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, pool::MaxPool}, matrix::Matrix, input::Input};
+///
+///let mut pool = MaxPool::new((2, 2), 2);
+///let input = Matrix::from(vec![
+///    vec![1.0, 2.0, 5.0, 6.0],
+///    vec![3.0, 4.0, 7.0, 8.0],
+///    vec![9.0, 10.0, 13.0, 14.0],
+///    vec![11.0, 12.0, 15.0, 16.0]]);
+///
+///let res = pool.forward(&input);
+///assert_eq!(res.to_param_2d(), vec![vec![4.0, 8.0], vec![12.0, 16.0]]);
+///
+///let errors = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+///let routed = pool.backward(pool.update_gradient(), Box::new(errors), Box::new(input));
+///assert_eq!(routed.to_param_2d(), vec![
+///    vec![0.0, 0.0, 0.0, 0.0],
+///    vec![0.0, 1.0, 0.0, 2.0],
+///    vec![0.0, 0.0, 0.0, 0.0],
+///    vec![0.0, 3.0, 0.0, 4.0]]);
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MaxPool{
+    kernel_size: (usize, usize),
+    stride: usize,
+    input_shape: (usize, usize),
+    pub data: Matrix,
+    //Position of the max value in the input for every cell of the output
+    mask: Vec<Vec<(usize, usize)>>,
+}
+
+impl MaxPool{
+    pub fn new(kernel_size: (usize, usize), stride: usize) -> MaxPool {
+        MaxPool {
+            kernel_size,
+            stride: stride.max(1),
+            input_shape: (0, 0),
+            data: Matrix::new_empty(0, 0),
+            mask: vec![],
+        }
+    }
+    fn get_res_size(w: usize, k: usize, s: usize) -> usize {
+        if w < k {
+            return 0;
+        }
+        (w - k) / s + 1
+    }
+}
+
+#[typetag::serde]
+impl Layer for MaxPool{
+    ///Slides the kernel over the input, keeping the max of every window and remembering where it
+    ///came from for back propegation
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = Matrix::from(inputs.to_param_2d());
+        self.input_shape = (input.rows, input.columns);
+
+        let res_rows = MaxPool::get_res_size(input.rows, self.kernel_size.0, self.stride);
+        let res_cols = MaxPool::get_res_size(input.columns, self.kernel_size.1, self.stride);
+
+        let mut output = Matrix::new_empty(res_rows, res_cols);
+        self.mask = vec![vec![(0, 0); res_cols]; res_rows];
+
+        for i in 0..res_rows {
+            for j in 0..res_cols {
+                let mut max = f32::NEG_INFINITY;
+                for k_row in i * self.stride..i * self.stride + self.kernel_size.0 {
+                    for k_col in j * self.stride..j * self.stride + self.kernel_size.1 {
+                        if input.data[k_row][k_col] > max {
+                            max = input.data[k_row][k_col];
+                            self.mask[i][j] = (k_row, k_col);
+                        }
+                    }
+                }
+                output.data[i][j] = max;
+            }
+        }
+
+        self.data = output;
+        Box::new(self.data.clone())
+    }
+    ///Routes each error back to the input position that was the max of its window, every other
+    ///position receives no error
+    fn backward(&mut self, _gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let errors_mat = Matrix::from(errors.to_param_2d());
+        let mut res = Matrix::new_empty(self.input_shape.0, self.input_shape.1);
+
+        for (i, row) in self.mask.iter().enumerate() {
+            for (j, (max_row, max_col)) in row.iter().enumerate() {
+                res.data[*max_row][*max_col] += errors_mat.data[i][j];
+            }
+        }
+
+        Box::new(res)
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.data.rows, self.data.columns, 0)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        0.0
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        Box::new(Matrix::new_empty(self.data.rows, self.data.columns) + 1.0)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
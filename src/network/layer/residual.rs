@@ -0,0 +1,162 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, activations::Activations, input::Input, modes::Mode, optimizer::Optimizer};
+
+use super::layers::Layer;
+
+///A Residual (skip connection) layer, wraps an inner layer and adds the wrapped layer's input
+///straight onto its output. When the shapes don't match the skip is dropped and the layer acts
+///exactly like the one it wraps
+///Implements the Layer trait
+///
+///The inner layer is expected to keep its output with one column per sample, like Dense,
+///Dropout, BatchNorm and Rnn do. During back propegation the incoming errors flow both through
+///the inner layer and unchanged down the skip path, and the two are summed
+///
+///# Example
+///
+///With the inner weights at 0 the only way back is the skip path, which passes the errors
+///through untouched
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, dense::Dense, residual::Residual}, activations::Activations, input::Input, matrix::Matrix};
+///
+///let mut dense = Dense::new(3, 3, Activations::SIGMOID, 0.1);
+///dense.weights = Matrix::new_empty(3, 3);
+///let mut residual = Residual::new(Box::new(dense));
+///
+///let input = vec![0.5, -1.0, 2.0];
+///let res = residual.forward(&input).to_param();
+///let inner = residual.inner.get_data().to_param();
+///assert_eq!(res, input.iter().zip(inner.iter()).map(|(skip, inner)| skip + inner).collect::<Vec<f32>>());
+///
+///let errors = Matrix::from(vec![vec![0.1], vec![-0.2], vec![0.3]]);
+///let data = Matrix::from(vec![input]).transpose();
+///let back = residual.backward(residual.update_gradient(), Box::new(errors.clone()), Box::new(data));
+///assert_eq!(back.to_param(), errors.to_param());
+///```
+///
+///Inside a network the wrapped layer is declared with LayerTypes::RESIDUAL
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+///
+///let mut new_net = Network::new(2);
+///new_net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.05));
+///new_net.add_layer(LayerTypes::RESIDUAL(Box::new(LayerTypes::DENSE(4, Activations::TANH, 0.05))));
+///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.05));
+///new_net.compile();
+///
+///new_net.fit_batched(&[vec![0.0, 1.0], vec![1.0, 0.0]], &[vec![1.0], vec![0.0]], 5, 1).unwrap();
+///assert_eq!(new_net.layers[1].parameter_count(), 20);
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Residual{
+    pub inner: Box<dyn Layer>,
+    pub data: Matrix,
+    skip: bool,
+}
+
+impl Residual{
+    pub fn new(inner: Box<dyn Layer>) -> Residual {
+        Residual {
+            inner,
+            data: Matrix::new_empty(0, 0),
+            skip: false,
+        }
+    }
+}
+
+#[typetag::serde]
+impl Layer for Residual{
+    ///Feeds the input through the inner layer and adds the input onto the result
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = Matrix::from(inputs.to_param_2d()).transpose();
+        self.inner.forward(inputs);
+        let inner_data = Matrix::from(self.inner.get_data().to_param_2d());
+
+        self.skip = inner_data.rows == input.rows && inner_data.columns == input.columns;
+        self.data = if self.skip { inner_data + &input } else { inner_data };
+
+        Box::new(self.data.transpose())
+    }
+    ///Back propegates through the inner layer, adding the errors that flowed down the skip path
+    ///onto the errors it returns
+    fn backward(&mut self, gradients: Box<dyn Input>, errors: Box<dyn Input>, data: Box<dyn Input>) -> Box<dyn Input> {
+        let errors_mat = Matrix::from(errors.to_param_2d());
+        let inner_errors = Matrix::from(self.inner.backward(gradients, Box::new(errors_mat.clone()), data).to_param_2d());
+
+        if self.skip && inner_errors.rows == errors_mat.rows && inner_errors.columns == errors_mat.columns {
+            Box::new(inner_errors + &errors_mat)
+        } else {
+            Box::new(inner_errors)
+        }
+    }
+    fn apply_gradients(&mut self, scale: f32) {
+        self.inner.apply_gradients(scale);
+    }
+    fn set_mode(&mut self, mode: Mode) {
+        self.inner.set_mode(mode);
+    }
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.inner.set_learning_rate(learning_rate);
+    }
+    fn set_gradient_clip(&mut self, max_norm: Option<f32>) {
+        self.inner.set_gradient_clip(max_norm);
+    }
+    fn set_weight_decay(&mut self, lambda: f32) {
+        self.inner.set_weight_decay(lambda);
+    }
+    fn set_momentum(&mut self, momentum: Option<f32>) {
+        self.inner.set_momentum(momentum);
+    }
+    fn set_optimizer(&mut self, optimizer: Optimizer) {
+        self.inner.set_optimizer(optimizer);
+    }
+    fn set_trainable(&mut self, trainable: bool) {
+        self.inner.set_trainable(trainable);
+    }
+    fn reset_state(&mut self) {
+        self.inner.reset_state();
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn get_activation(&self) -> Option<Activations> {
+        self.inner.get_activation()
+    }
+    fn set_activation(&mut self, activation: Activations) {
+        self.inner.set_activation(activation);
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        self.inner.shape()
+    }
+    fn input_size(&self) -> Option<usize> {
+        self.inner.input_size()
+    }
+    fn get_loss(&self) -> f32 {
+        self.inner.get_loss()
+    }
+    fn parameter_count(&self) -> usize {
+        self.inner.parameter_count()
+    }
+    fn parameters(&self) -> Vec<f32> {
+        self.inner.parameters()
+    }
+    fn parameters_finite(&self) -> bool {
+        self.inner.parameters_finite()
+    }
+    fn set_parameters(&mut self, parameters: &[f32]) {
+        self.inner.set_parameters(parameters);
+    }
+    fn parameter_gradients(&self) -> Vec<f32> {
+        self.inner.parameter_gradients()
+    }
+    ///The skip path adds nothing to the derivative of the inner layer's activation
+    fn update_gradient(&self) -> Box<dyn Input> {
+        self.inner.update_gradient()
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,201 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, activations::Activations, input::Input};
+
+use super::layers::Layer;
+
+///A simple Recurrent layer, every forward pass is one step of a sequence and the hidden state it
+///produces is carried into the next step
+///Implements the Layer trait
+///
+///h_t = activation(W_x x_t + W_h h_{t-1} + b)
+///
+///Back propegation is truncated to a single step, the previous hidden state is treated as a
+///constant input. Call reset_state between sequences so one doesn't leak into the next
+///
+///# Example
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, rnn::Rnn}, activations::Activations, input::Input};
+///
+///let mut rnn = Rnn::new(2, 3, Activations::TANH, 0.1);
+///let first = rnn.forward(&vec![1.0, 0.5]).to_param();
+///let second = rnn.forward(&vec![1.0, 0.5]).to_param();
+///assert_eq!(first.len(), 3);
+///assert_ne!(first, second);
+///
+///rnn.reset_state();
+///assert_eq!(rnn.forward(&vec![1.0, 0.5]).to_param(), first);
+///```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Rnn{
+    pub input_weights: Matrix,
+    pub hidden_weights: Matrix,
+    pub biases: Matrix,
+    //The hidden state of the current step, one column
+    pub data: Matrix,
+    pub activation_fn: Activations,
+    learning_rate: f32,
+    loss: f32,
+    #[serde(default)]
+    frozen: bool,
+
+    #[serde(skip)]
+    input: Matrix,
+    //The values before the activation function, which its derivative is taken of
+    #[serde(skip)]
+    pre_activation: Matrix,
+    #[serde(skip)]
+    prev_hidden: Matrix,
+    #[serde(skip)]
+    input_gradient: Matrix,
+    #[serde(skip)]
+    hidden_gradient: Matrix,
+    #[serde(skip)]
+    bias_gradient: Matrix,
+}
+
+impl Rnn{
+    ///Creates an Rnn layer taking `inputs` values every step and keeping a hidden state of
+    ///`hidden_size` values
+    pub fn new(inputs: usize, hidden_size: usize, activation: Activations, learning_rate: f32) -> Rnn {
+        Rnn {
+            input_weights: Matrix::new_random(hidden_size, inputs),
+            hidden_weights: Matrix::new_random(hidden_size, hidden_size),
+            biases: Matrix::new_random(hidden_size, 1),
+            data: Matrix::new_empty(hidden_size, 1),
+            activation_fn: activation,
+            learning_rate,
+            loss: 1.0,
+            frozen: false,
+            input: Matrix::new_empty(inputs, 1),
+            pre_activation: Matrix::new_empty(hidden_size, 1),
+            prev_hidden: Matrix::new_empty(hidden_size, 1),
+            input_gradient: Matrix::new_empty(hidden_size, inputs),
+            hidden_gradient: Matrix::new_empty(hidden_size, hidden_size),
+            bias_gradient: Matrix::new_empty(hidden_size, 1),
+        }
+    }
+    ///Clears the hidden state, the next forward pass starts a new sequence
+    pub fn reset_state(&mut self) {
+        self.data = Matrix::new_empty(self.biases.rows, 1);
+    }
+}
+
+#[typetag::serde]
+impl Layer for Rnn{
+    ///Moves the sequence forward a single step, the whole input is the value of this step
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let input = Matrix::from(inputs.to_param().to_param_2d()).transpose();
+        //The width of the layer before can be unknown until data flows through the network
+        if self.input_weights.columns != input.rows {
+            self.input_weights = Matrix::new_random(self.biases.rows, input.rows);
+            self.input_gradient = Matrix::new_empty(self.biases.rows, input.rows);
+        }
+        //A freshly loaded layer has no hidden state yet
+        if self.data.rows != self.biases.rows {
+            self.reset_state();
+        }
+
+        self.prev_hidden = self.data.clone();
+        let hidden = self.input_weights.clone() * &input + &(self.hidden_weights.clone() * &self.prev_hidden) + &self.biases;
+        self.input = input;
+        self.data = self.activation_fn.apply(hidden.clone());
+        self.pre_activation = hidden;
+
+        Box::new(self.data.transpose())
+    }
+    ///Accumulates the gradients of this step only, returning the errors of this step's input
+    fn backward(&mut self, gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        let mut gradients_mat = Matrix::from(gradients.to_param_2d());
+        let errors_mat = Matrix::from(errors.to_param().to_param_2d()).transpose();
+        let delta = gradients_mat.dot_multiply(&errors_mat);
+
+        //Gradients are skipped during serialization, so a freshly loaded layer starts out empty
+        if self.bias_gradient.rows == 0 {
+            self.input_gradient = Matrix::new_empty(self.input_weights.rows, self.input_weights.columns);
+            self.hidden_gradient = Matrix::new_empty(self.hidden_weights.rows, self.hidden_weights.columns);
+            self.bias_gradient = Matrix::new_empty(self.biases.rows, 1);
+        }
+        if !self.frozen {
+            self.input_gradient = self.input_gradient.clone() + &(delta.clone() * &self.input.transpose());
+            self.hidden_gradient = self.hidden_gradient.clone() + &(delta.clone() * &self.prev_hidden.transpose());
+            self.bias_gradient = self.bias_gradient.clone() + &delta;
+        }
+
+        let input_errors = self.input_weights.transpose() * &delta;
+        let values = input_errors.to_param();
+        self.loss = values.iter().map(|error| error.powi(2)).sum::<f32>() / values.len().max(1) as f32;
+
+        Box::new(input_errors)
+    }
+    ///Moves the weights and biases along the accumulated gradients, scaled by `scale` and the
+    ///learning rate
+    fn apply_gradients(&mut self, scale: f32) {
+        if self.bias_gradient.rows == 0 {
+            return;
+        }
+        let step = scale * self.learning_rate;
+        self.input_weights = self.input_weights.clone() + &(self.input_gradient.clone() * step);
+        self.hidden_weights = self.hidden_weights.clone() + &(self.hidden_gradient.clone() * step);
+        self.biases = self.biases.clone() + &(self.bias_gradient.clone() * step);
+
+        self.input_gradient = Matrix::new_empty(self.input_weights.rows, self.input_weights.columns);
+        self.hidden_gradient = Matrix::new_empty(self.hidden_weights.rows, self.hidden_weights.columns);
+        self.bias_gradient = Matrix::new_empty(self.biases.rows, 1);
+    }
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.learning_rate = learning_rate;
+    }
+    fn set_trainable(&mut self, trainable: bool) {
+        self.frozen = !trainable;
+    }
+    fn reset_state(&mut self) {
+        Rnn::reset_state(self);
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    fn get_activation(&self) -> Option<Activations> {
+        Some(self.activation_fn)
+    }
+    fn set_activation(&mut self, activation: Activations) {
+        self.activation_fn = activation;
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.input_weights.rows, self.input_weights.columns, 0)
+    }
+    fn get_loss(&self) -> f32 {
+        self.loss
+    }
+    fn parameter_count(&self) -> usize {
+        self.input_weights.rows * self.input_weights.columns + self.hidden_weights.rows * self.hidden_weights.columns + self.biases.rows
+    }
+    fn parameters(&self) -> Vec<f32> {
+        [self.input_weights.to_param(), self.hidden_weights.to_param(), self.biases.to_param()].concat()
+    }
+    fn parameters_finite(&self) -> bool {
+        self.input_weights.is_finite() && self.hidden_weights.is_finite() && self.biases.is_finite()
+    }
+    fn set_parameters(&mut self, parameters: &[f32]) {
+        let input_split = self.input_weights.rows * self.input_weights.columns;
+        let hidden_split = input_split + self.hidden_weights.rows * self.hidden_weights.columns;
+        self.input_weights = Matrix::from_slice(&parameters[..input_split], self.input_weights.rows, self.input_weights.columns);
+        self.hidden_weights = Matrix::from_slice(&parameters[input_split..hidden_split], self.hidden_weights.rows, self.hidden_weights.columns);
+        self.biases = Matrix::from_slice(&parameters[hidden_split..], self.biases.rows, 1);
+    }
+    fn parameter_gradients(&self) -> Vec<f32> {
+        if self.bias_gradient.rows == 0 {
+            return vec![0.0; self.parameter_count()];
+        }
+        [self.input_gradient.to_param(), self.hidden_gradient.to_param(), self.bias_gradient.to_param()].concat()
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        let mut res = self.pre_activation.clone();
+        res.apply_inplace(&*self.activation_fn.get_function().derivative);
+        Box::new(res)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
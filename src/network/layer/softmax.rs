@@ -0,0 +1,109 @@
+use serde::{Serialize, Deserialize};
+
+use crate::network::{matrix::Matrix, activations::Activations, input::Input};
+
+use super::layers::Layer;
+
+///A fused Softmax and Cross-Entropy output layer, turns the logits of the layer before it into
+///probabilities that sum to 1
+///Implements the Layer trait
+///
+///The probabilities are computed as exp(z - logsumexp(z)), where logsumexp subtracts the largest
+///logit before exponentiating, so even huge logits can't overflow. Paired with Loss::CROSSENTROPY
+///the error of the logits is simply (target - probability), which backward passes straight to the
+///layer before. That layer should output raw logits, LEAKYRELU(1.0) acts as a linear activation
+///
+///# Example
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, softmax::SoftmaxCE}, input::Input};
+///
+///let mut softmax = SoftmaxCE::new();
+///let res = softmax.forward(&vec![1000.0, 0.0, -1000.0]).to_param();
+///assert!(res.iter().all(|probability| probability.is_finite()));
+///assert!((res[0] - 1.0).abs() < 1e-6);
+///assert!(res[1] < 1e-6 && res[2] < 1e-6);
+///
+///let res = softmax.forward(&vec![2.0f32.ln(), 0.0]).to_param();
+///assert!((res[0] - 2.0 / 3.0).abs() < 1e-6);
+///```
+///
+///As the output layer of a classifier
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, loss::Loss, modes::Task};
+///
+///let inputs = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+///let outputs = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+///
+///let mut new_net = Network::new(2);
+///new_net.add_layer(LayerTypes::DENSE(8, Activations::TANH, 0.05));
+///new_net.add_layer(LayerTypes::DENSE(3, Activations::LEAKYRELU(1.0), 0.05));
+///new_net.add_layer(LayerTypes::SOFTMAXCE);
+///new_net.set_loss(Loss::CROSSENTROPY);
+///new_net.set_task(Task::CLASSIFICATION);
+///new_net.compile();
+///new_net.fit(&inputs, &outputs, 200).unwrap();
+///
+///for (class, input) in inputs.iter().enumerate() {
+///    assert_eq!(new_net.predict_class(input.clone()).unwrap(), class);
+///}
+///```
+#[derive(Clone, Serialize, Deserialize, Default)]
+pub struct SoftmaxCE{
+    pub data: Matrix,
+}
+
+impl SoftmaxCE{
+    pub fn new() -> SoftmaxCE {
+        SoftmaxCE {
+            data: Matrix::new_empty(0, 0),
+        }
+    }
+}
+
+#[typetag::serde]
+impl Layer for SoftmaxCE{
+    ///Normalizes every sample (column) of logits into probabilities with the log-sum-exp trick
+    fn forward(&mut self, inputs: &dyn Input) -> Box<dyn Input> {
+        let logits = Matrix::from(inputs.to_param_2d()).transpose();
+
+        self.data = Matrix::new_empty(logits.rows, logits.columns);
+        for col in 0..logits.columns {
+            let max = (0..logits.rows).map(|row| logits.data[row][col]).fold(f32::NEG_INFINITY, f32::max);
+            let log_sum_exp = max + (0..logits.rows).map(|row| (logits.data[row][col] - max).exp()).sum::<f32>().ln();
+            for row in 0..logits.rows {
+                self.data.data[row][col] = (logits.data[row][col] - log_sum_exp).exp();
+            }
+        }
+
+        Box::new(self.data.transpose())
+    }
+    ///The errors of the output, (target - probability) under cross-entropy, already are the
+    ///errors of the logits, so they pass through unchanged
+    fn backward(&mut self, _gradients: Box<dyn Input>, errors: Box<dyn Input>, _data: Box<dyn Input>) -> Box<dyn Input> {
+        Box::new(Matrix::from(errors.to_param_2d()))
+    }
+    fn get_data(&self) -> Box<dyn Input> {
+        Box::new(self.data.clone())
+    }
+    ///Reports SOFTMAX so the loss is computed as categorical cross-entropy
+    fn get_activation(&self) -> Option<Activations> {
+        Some(Activations::SOFTMAX)
+    }
+    fn shape(&self) -> (usize, usize, usize) {
+        (self.data.rows, self.data.rows, 0)
+    }
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+    fn get_loss(&self) -> f32 {
+        0.0
+    }
+    fn update_gradient(&self) -> Box<dyn Input> {
+        Box::new(Matrix::new_empty(self.data.rows, self.data.columns) + 1.0)
+    }
+    fn clone_box(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Serialize, Deserialize};
+
+use super::activations::Activations;
+
+//Keeps logarithms and divisions away from 0 when outputs saturate
+const EPSILON: f32 = 1e-7;
+
+///The loss functions a network can be trained against
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Loss{
+    #[default]
+    MSE,
+    CROSSENTROPY,
+    MAE,
+    ///Cross-entropy against a target that's the index of the class, a single value, instead of a
+    ///one-hot vector. Trains exactly like CROSSENTROPY on the matching one-hot targets
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, loss::Loss, modes::Task, data::one_hot, error::NetworkError};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0], vec![0.0, 0.0]];
+    ///let labels = vec![0, 1, 2, 0];
+    ///let sparse: Vec<Vec<f32>> = labels.iter().map(|&label| vec![label as f32]).collect();
+    ///let dense = one_hot(&labels, 3);
+    ///
+    ///let mut sparse_net = Network::new_seeded(2, 5);
+    ///sparse_net.add_layer(LayerTypes::DENSE(6, Activations::TANH, 0.05));
+    ///sparse_net.add_layer(LayerTypes::DENSE(3, Activations::SOFTMAX, 0.05));
+    ///sparse_net.set_task(Task::CLASSIFICATION);
+    ///sparse_net.compile();
+    ///let mut dense_net = sparse_net.clone();
+    ///
+    ///sparse_net.set_loss(Loss::SPARSECROSSENTROPY);
+    ///dense_net.set_loss(Loss::CROSSENTROPY);
+    ///let output = sparse_net.predict(inputs[2].clone()).unwrap();
+    ///assert_eq!(Loss::SPARSECROSSENTROPY.compute(&output, &sparse[2], Some(Activations::SOFTMAX)), Loss::CROSSENTROPY.compute(&output, &dense[2], Some(Activations::SOFTMAX)));
+    ///assert_eq!(Loss::SPARSECROSSENTROPY.error(&output, &sparse[2], Some(Activations::SOFTMAX)), Loss::CROSSENTROPY.error(&output, &dense[2], Some(Activations::SOFTMAX)));
+    ///
+    ///sparse_net.fit_seeded(&inputs, &sparse, 100, 5).unwrap();
+    ///dense_net.fit_seeded(&inputs, &dense, 100, 5).unwrap();
+    ///assert_eq!(sparse_net.get_loss_history(), dense_net.get_loss_history());
+    ///assert_eq!(sparse_net.predict(inputs[0].clone()).unwrap(), dense_net.predict(inputs[0].clone()).unwrap());
+    ///assert_eq!(sparse_net.accuracy(&inputs, &sparse), dense_net.accuracy(&inputs, &dense));
+    ///
+    ///assert_eq!(sparse_net.fit(&inputs[..1], &[vec![3.0]], 1), Err(NetworkError::InvalidClassLabel { label: 3.0, classes: 3 }));
+    ///assert!(sparse_net.fit(&inputs[..1], &[vec![-1.0]], 1).is_err());
+    ///assert!(sparse_net.fit(&inputs[..1], &[vec![0.5]], 1).is_err());
+    ///```
+    SPARSECROSSENTROPY
+}
+
+impl Loss{
+    ///How many values every target has for an output layer of `output_size` values
+    pub fn target_size(&self, output_size: usize) -> usize {
+        match self {
+            Loss::SPARSECROSSENTROPY => 1,
+            _ => output_size,
+        }
+    }
+    ///Computes the loss of a single output against its target
+    ///
+    ///Cross-entropy is categorical when the output layer uses SOFTMAX and binary (per output)
+    ///otherwise
+    pub fn compute(&self, outputs: &[f32], targets: &[f32], activation: Option<Activations>) -> f32 {
+        let pairs = outputs.iter().zip(targets.iter());
+        match self {
+            Loss::MSE => pairs.map(|(output, target)| (output - target).powi(2)).sum::<f32>() / outputs.len() as f32,
+            Loss::MAE => pairs.map(|(output, target)| (output - target).abs()).sum::<f32>() / outputs.len() as f32,
+            Loss::CROSSENTROPY => match activation {
+                Some(Activations::SOFTMAX) => -pairs.map(|(output, target)| target * output.max(EPSILON).ln()).sum::<f32>(),
+                _ => -pairs.map(|(output, target)| {
+                    let output = output.clamp(EPSILON, 1.0 - EPSILON);
+                    target * output.ln() + (1.0 - target) * (1.0 - output).ln()
+                }).sum::<f32>() / outputs.len() as f32
+            },
+            Loss::SPARSECROSSENTROPY => {
+                let class = sparse_class(targets);
+                match activation {
+                    Some(Activations::SOFTMAX) => -outputs.get(class).map_or(EPSILON, |output| output.max(EPSILON)).ln(),
+                    _ => -outputs.iter().enumerate().map(|(index, output)| {
+                        let output = output.clamp(EPSILON, 1.0 - EPSILON);
+                        if index == class { output.ln() } else { (1.0 - output).ln() }
+                    }).sum::<f32>() / outputs.len() as f32
+                }
+            }
+        }
+    }
+    ///Computes the error at the output layer, the direction every output should move to lower
+    ///the loss (the negative gradient of the loss with respect to the output)
+    ///
+    ///Cross-entropy paired with SOFTMAX (or SIGMOID) simplifies to (target - predicted) once the
+    ///output activation's derivative is applied
+    pub fn error(&self, outputs: &[f32], targets: &[f32], activation: Option<Activations>) -> Vec<f32> {
+        let pairs = outputs.iter().zip(targets.iter());
+        match self {
+            Loss::MSE => pairs.map(|(output, target)| target - output).collect(),
+            Loss::MAE => pairs.map(|(output, target)| {
+                if target == output {
+                    0.0
+                } else {
+                    (target - output).signum()
+                }
+            }).collect(),
+            Loss::CROSSENTROPY => match activation {
+                Some(Activations::SOFTMAX) => pairs.map(|(output, target)| target - output).collect(),
+                _ => pairs.map(|(output, target)| {
+                    let output = output.clamp(EPSILON, 1.0 - EPSILON);
+                    (target - output) / (output * (1.0 - output))
+                }).collect()
+            },
+            //The one-hot target is only ever implied by comparing every index to the class
+            Loss::SPARSECROSSENTROPY => {
+                let class = sparse_class(targets);
+                outputs.iter().enumerate().map(|(index, output)| {
+                    let target = (index == class) as u8 as f32;
+                    match activation {
+                        Some(Activations::SOFTMAX) => target - output,
+                        _ => {
+                            let output = output.clamp(EPSILON, 1.0 - EPSILON);
+                            (target - output) / (output * (1.0 - output))
+                        }
+                    }
+                }).collect()
+            }
+        }
+    }
+}
+
+///The class index a sparse target holds, Network checks it's a valid class before training on it
+pub(crate) fn sparse_class(targets: &[f32]) -> usize {
+    targets.first().map_or(0, |class| class.round().max(0.0) as usize)
+}
@@ -1,8 +1,11 @@
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use ndarray_rand::rand_distr::{Distribution, Normal};
 use std::ops;
 use serde::{Serialize, Deserialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+use super::{error::MatrixError, rng};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Matrix{
     pub rows: usize,
     pub columns: usize,
@@ -65,23 +68,13 @@ impl ops::Sub<&Matrix> for Matrix{
 impl ops::Mul<&Matrix> for Matrix{
     type Output = Matrix;
 
+    ///Multiplies two matrices, panicking with a MatrixError::DimensionMismatch message if they
+    ///don't conform, use matmul to handle the error instead
     fn mul(self, other: &Matrix) -> Matrix{
-        if self.columns != other.rows{
-            panic!("Error attempting to multiply two matrices with different dimensions \nMatrix A: {} x {}\nMatrix B: {} x {}", self.rows, self.columns, other.rows, other.columns);
+        match self.matmul(other) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
         }
-
-        let mut res = Matrix::new_empty(self.rows, other.columns);                 
-
-        for i in 0..self.rows{
-            for j in 0..other.columns{
-                let mut sum = 0.0;
-                for k in 0..self.columns{
-                    sum += self.data[i][k] * other.data[k][j];                    
-                }
-                res.data[i][j] = sum;
-            }
-        }
-        res
     }
 }
 
@@ -140,18 +133,100 @@ impl ops::Add<f32> for Matrix {
 impl ops::Div<&Matrix> for Matrix{
     type Output = Matrix;
     fn div(self, rhs: &Matrix) -> Self::Output {
-        let mut res: Matrix = Matrix::new_empty(self.rows, self.columns);
+        self.elementwise_div(rhs)
+    }
+}
+
+impl Matrix{
+    pub fn sqrt(&self) -> Matrix{
+        self.elementwise_sqrt()
+    }
+    ///Multiplies every value by the value in the same position of `other` (the Hadamard
+    ///product), panicking if the dimensions don't match
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let a = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    ///let b = Matrix::from(vec![vec![2.0, 0.5], vec![-1.0, 0.0]]);
+    ///assert_eq!(a.hadamard(&b).data, vec![vec![2.0, 1.0], vec![-3.0, 0.0]]);
+    ///```
+    pub fn hadamard(&self, other: &Matrix) -> Matrix{
+        if self.rows != other.rows || self.columns != other.columns{
+            panic!("Error attempting to multiply two matrices elementwise with different dimensions \nMatrix A: {} x {}\nMatrix B: {} x {}", self.rows, self.columns, other.rows, other.columns);
+        }
+        let mut res = Matrix::new_empty(self.rows, self.columns);
         for i in 0..self.rows{
             for j in 0..self.columns{
-                res.data[i][j] = self.data[i][j] / rhs.data[i][j];
+                res.data[i][j] = self.data[i][j] * other.data[i][j];
             }
         }
         res
     }
-}
-
-impl Matrix{
-    pub fn sqrt(&self) -> Matrix{
+    ///Adds a column vector (an n x 1 Matrix) to every column of this n x m Matrix, panicking if
+    ///`col_vector` isn't a single column with as many rows
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mat = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    ///let bias = Matrix::from(vec![vec![10.0], vec![-1.0]]);
+    ///assert_eq!(mat.add_broadcast(&bias).data, vec![vec![11.0, 12.0, 13.0], vec![3.0, 4.0, 5.0]]);
+    ///```
+    pub fn add_broadcast(&self, col_vector: &Matrix) -> Matrix{
+        if col_vector.columns != 1 || col_vector.rows != self.rows{
+            panic!("Error attempting to broadcast a matrix that isn't a matching column vector \nMatrix A: {} x {}\nMatrix B: {} x {}", self.rows, self.columns, col_vector.rows, col_vector.columns);
+        }
+        let mut res = self.clone();
+        for (row, bias) in res.data.iter_mut().zip(col_vector.data.iter()){
+            for value in row.iter_mut(){
+                *value += bias[0];
+            }
+        }
+        res
+    }
+    ///Divides every value by the value in the same position of `other`, panicking if the
+    ///dimensions don't match. Nothing guards against dividing by 0, add an epsilon to `other`
+    ///when it can get close
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let a = Matrix::from(vec![vec![1.0, 6.0], vec![-3.0, 1.0]]);
+    ///let b = Matrix::from(vec![vec![2.0, 3.0], vec![1.5, 0.0]]);
+    ///assert_eq!(a.elementwise_div(&b).data[0], vec![0.5, 2.0]);
+    ///
+    ///let guarded = a.elementwise_div(&(b + 1e-8));
+    ///assert!(guarded.data[1][1].is_finite());
+    ///assert!(guarded.data[1][1] > 1e7);
+    ///```
+    pub fn elementwise_div(&self, other: &Matrix) -> Matrix{
+        if self.rows != other.rows || self.columns != other.columns{
+            panic!("Error attempting to divide two matrices elementwise with different dimensions \nMatrix A: {} x {}\nMatrix B: {} x {}", self.rows, self.columns, other.rows, other.columns);
+        }
+        let mut res = Matrix::new_empty(self.rows, self.columns);
+        for i in 0..self.rows{
+            for j in 0..self.columns{
+                res.data[i][j] = self.data[i][j] / other.data[i][j];
+            }
+        }
+        res
+    }
+    ///The square root of every value
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mat = Matrix::from(vec![vec![4.0, 9.0], vec![0.0, 2.25]]);
+    ///assert_eq!(mat.elementwise_sqrt().data, vec![vec![2.0, 3.0], vec![0.0, 1.5]]);
+    ///```
+    pub fn elementwise_sqrt(&self) -> Matrix{
         let mut res: Matrix = Matrix::new_empty(self.rows, self.columns);
         for i in 0..self.rows{
             for j in 0..self.columns{
@@ -160,22 +235,112 @@ impl Matrix{
         }
         res
     }
+    ///The L2 (Frobenius) norm of the Matrix, the square root of the sum of every squared value
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mat = Matrix::from(vec![vec![3.0], vec![4.0]]);
+    ///assert_eq!(mat.l2_norm(), 5.0);
+    ///```
+    pub fn l2_norm(&self) -> f32{
+        self.data.iter().flatten().map(|x| x * x).sum::<f32>().sqrt()
+    }
     pub fn new_empty(rows: usize, cols: usize) -> Matrix{
         Matrix{
-            rows: rows,
+            rows,
             columns: cols,
             data: vec![vec![0.0; cols]; rows]
         }
     }
 
     pub fn new_random(rows: usize, cols: usize) -> Matrix{
-        let mut rng = thread_rng();
         let mut res = Matrix::new_empty(rows, cols); 
-        for row in 0..rows{
-            for col in 0..cols{
-                res.data[row][col] = rng.gen::<f32>() * 2.0 - 1.0;
+        rng::with_rng(|rng| {
+            for row in 0..rows{
+                for col in 0..cols{
+                    res.data[row][col] = rng.gen::<f32>() * 2.0 - 1.0;
+                }
             }
+        });
+        res
+    }
+    ///Multiplies this Matrix by `other`, failing with MatrixError::DimensionMismatch unless this
+    ///Matrix has as many columns as `other` has rows
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{matrix::Matrix, error::MatrixError};
+    ///
+    ///let a = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    ///let b = Matrix::from(vec![vec![1.0], vec![1.0]]);
+    ///assert_eq!(a.matmul(&b).unwrap().data, vec![vec![3.0], vec![7.0]]);
+    ///
+    ///let err = b.matmul(&b).unwrap_err();
+    ///assert_eq!(err, MatrixError::DimensionMismatch { lhs: (2, 1), rhs: (2, 1) });
+    ///```
+    pub fn matmul(&self, other: &Matrix) -> Result<Matrix, MatrixError>{
+        if self.columns != other.rows{
+            return Err(MatrixError::DimensionMismatch { lhs: (self.rows, self.columns), rhs: (other.rows, other.columns) });
         }
+        Ok(self.matmul_unchecked(other))
+    }
+    #[cfg(not(feature = "ndarray"))]
+    fn matmul_unchecked(&self, other: &Matrix) -> Matrix{
+        let mut res = Matrix::new_empty(self.rows, other.columns);                 
+
+        for i in 0..self.rows{
+            for j in 0..other.columns{
+                let mut sum = 0.0;
+                for k in 0..self.columns{
+                    sum += self.data[i][k] * other.data[k][j];                    
+                }
+                res.data[i][j] = sum;
+            }
+        }
+        res
+    }
+    #[cfg(feature = "ndarray")]
+    fn matmul_unchecked(&self, other: &Matrix) -> Matrix{
+        //Every row is its own Vec, so each side is flattened into one buffer ndarray can own
+        let to_array = |mat: &Matrix| ndarray::Array2::from_shape_vec((mat.rows, mat.columns), mat.data.concat()).unwrap();
+        //The product of two row major arrays is row major, so its buffer is already in row order
+        let res = to_array(self).dot(&to_array(other)).into_raw_vec();
+
+        Matrix::from_slice(&res, self.rows, other.columns)
+    }
+    ///Creates a Matrix of normally distributed values with a standard deviation of
+    ///sqrt(2 / fan_in), He initialization for layers using RELU
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let weights = Matrix::new_he(64, 200, 200);
+    ///let values: Vec<f32> = weights.data.iter().flatten().copied().collect();
+    ///let variance = values.iter().map(|x| x * x).sum::<f32>() / values.len() as f32;
+    ///assert!((variance - 0.01).abs() < 0.002);
+    ///```
+    pub fn new_he(rows: usize, cols: usize, fan_in: usize) -> Matrix{
+        Matrix::new_normal(rows, cols, (2.0 / fan_in.max(1) as f32).sqrt())
+    }
+    ///Creates a Matrix of normally distributed values with a standard deviation of
+    ///sqrt(1 / fan_in), Xavier initialization for layers using SIGMOID or TANH
+    pub fn new_xavier(rows: usize, cols: usize, fan_in: usize) -> Matrix{
+        Matrix::new_normal(rows, cols, (1.0 / fan_in.max(1) as f32).sqrt())
+    }
+    fn new_normal(rows: usize, cols: usize, std_dev: f32) -> Matrix{
+        let normal = Normal::new(0.0, std_dev).unwrap();
+        let mut res = Matrix::new_empty(rows, cols);
+        rng::with_rng(|rng| {
+            for row in 0..rows{
+                for col in 0..cols{
+                    res.data[row][col] = normal.sample(rng);
+                }
+            }
+        });
         res
     }
 
@@ -192,17 +357,7 @@ impl Matrix{
         res
     }*/
     pub fn dot_multiply(&mut self, other: &Matrix) -> Matrix {
-        if self.rows != other.rows || self.columns != self.columns{
-            panic!("Invalid matrix multiplaction, mismatched dimensions");
-        }
-        let mut res = Matrix::new_empty(self.rows, self.columns);
-
-        for i in 0..self.rows{
-            for j in 0..self.columns{
-                res.data[i][j] = self.data[i][j] * other.data[i][j];
-            }
-        }
-        res
+        self.hadamard(other)
     }
     /*pub fn subtract(&mut self, other: &Matrix) -> Matrix {
         if self.rows != other.rows || self.columns != self.columns{
@@ -223,16 +378,384 @@ impl Matrix{
             data
         }
     }
+    ///Creates a `rows` x `cols` Matrix from a slice of values laid out row by row
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mut mat = Matrix::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3);
+    ///assert_eq!(mat.row(1), &[4.0, 5.0, 6.0]);
+    ///assert_eq!(mat.col(2), vec![3.0, 6.0]);
+    ///assert_eq!(mat.get(0, 1), 2.0);
+    ///
+    ///mat.set(0, 1, 7.0);
+    ///assert_eq!(mat.get(0, 1), 7.0);
+    ///```
+    pub fn from_slice(data: &[f32], rows: usize, cols: usize) -> Matrix {
+        if data.len() != rows * cols {
+            panic!("Error attempting to create a {} x {} matrix from {} values", rows, cols, data.len());
+        }
+        if cols == 0 {
+            return Matrix::new_empty(rows, 0);
+        }
+        Matrix{
+            rows,
+            columns: cols,
+            data: data.chunks(cols).map(|row| row.to_vec()).collect()
+        }
+    }
+    ///Places `other` to the right of this Matrix, both need the same number of rows
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{matrix::Matrix, error::MatrixError};
+    ///
+    ///let a = Matrix::from(vec![vec![1.0], vec![2.0]]);
+    ///let b = Matrix::from(vec![vec![3.0, 4.0], vec![5.0, 6.0]]);
+    ///assert_eq!(a.concat_horizontal(&b).unwrap().data, vec![vec![1.0, 3.0, 4.0], vec![2.0, 5.0, 6.0]]);
+    ///
+    ///let c = Matrix::from(vec![vec![7.0]]);
+    ///assert_eq!(a.concat_horizontal(&c).unwrap_err(), MatrixError::RowCountMismatch { lhs: (2, 1), rhs: (1, 1) });
+    ///```
+    pub fn concat_horizontal(&self, other: &Matrix) -> Result<Matrix, MatrixError> {
+        if self.rows != other.rows {
+            return Err(MatrixError::RowCountMismatch { lhs: (self.rows, self.columns), rhs: (other.rows, other.columns) });
+        }
+        Ok(Matrix{
+            rows: self.rows,
+            columns: self.columns + other.columns,
+            data: self.data.iter().zip(other.data.iter()).map(|(left, right)| [left.as_slice(), right.as_slice()].concat()).collect()
+        })
+    }
+    ///Places `other` below this Matrix, both need the same number of columns
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{matrix::Matrix, error::MatrixError};
+    ///
+    ///let a = Matrix::from(vec![vec![1.0, 2.0]]);
+    ///let b = Matrix::from(vec![vec![3.0, 4.0], vec![5.0, 6.0]]);
+    ///assert_eq!(a.concat_vertical(&b).unwrap().data, vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+    ///
+    ///let c = Matrix::from(vec![vec![7.0]]);
+    ///assert_eq!(a.concat_vertical(&c).unwrap_err(), MatrixError::ColumnCountMismatch { lhs: (1, 2), rhs: (1, 1) });
+    ///```
+    pub fn concat_vertical(&self, other: &Matrix) -> Result<Matrix, MatrixError> {
+        if self.columns != other.columns {
+            return Err(MatrixError::ColumnCountMismatch { lhs: (self.rows, self.columns), rhs: (other.rows, other.columns) });
+        }
+        Ok(Matrix{
+            rows: self.rows + other.rows,
+            columns: self.columns,
+            data: self.data.iter().chain(other.data.iter()).cloned().collect()
+        })
+    }
+    ///The same values laid out as a `rows` x `cols` Matrix, read and refilled in row-major order
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{matrix::Matrix, error::MatrixError};
+    ///
+    ///let mat = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    ///assert_eq!(mat.reshape(3, 2).unwrap().data, vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+    ///assert_eq!(mat.reshape(6, 1).unwrap().flatten(), mat.flatten());
+    ///
+    ///assert_eq!(mat.reshape(4, 2).unwrap_err(), MatrixError::ReshapeMismatch { from: (2, 3), to: (4, 2) });
+    ///```
+    pub fn reshape(&self, rows: usize, cols: usize) -> Result<Matrix, MatrixError> {
+        if rows * cols != self.rows * self.columns {
+            return Err(MatrixError::ReshapeMismatch { from: (self.rows, self.columns), to: (rows, cols) });
+        }
+        Ok(Matrix::from_slice(&self.flatten(), rows, cols))
+    }
+    ///Every value of the Matrix in row-major order
+    pub fn flatten(&self) -> Vec<f32> {
+        self.data.concat()
+    }
+    ///Every value of the Matrix in row-major order as little-endian f32 bytes, the layout numpy
+    ///reads with `np.frombuffer(bytes, dtype='<f4').reshape(rows, cols)`. The shape isn't
+    ///stored, see from_bytes
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{matrix::Matrix, error::MatrixError};
+    ///
+    ///let mat = Matrix::from(vec![vec![1.0, -2.5, 3.0], vec![0.0, 1e-8, f32::MAX]]);
+    ///let bytes = mat.to_bytes();
+    ///assert_eq!(bytes.len(), 6 * 4);
+    ///assert_eq!(&bytes[..4], &1.0f32.to_le_bytes());
+    ///assert_eq!(Matrix::from_bytes(2, 3, &bytes).unwrap().data, mat.data);
+    ///
+    ///assert_eq!(Matrix::from_bytes(2, 2, &bytes).unwrap_err(), MatrixError::ByteLengthMismatch { expected: 16, got: 24 });
+    ///assert!(Matrix::from_bytes(2, 3, &bytes[..23]).is_err());
+    ///```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.iter().flatten().flat_map(|value| value.to_le_bytes()).collect()
+    }
+    ///A `rows` x `cols` Matrix read from little-endian f32 bytes in row-major order, the inverse of
+    ///to_bytes
+    ///
+    ///Returns MatrixError::ByteLengthMismatch unless there are exactly 4 bytes for every value
+    pub fn from_bytes(rows: usize, cols: usize, bytes: &[u8]) -> Result<Matrix, MatrixError> {
+        let expected = rows * cols * 4;
+        if bytes.len() != expected {
+            return Err(MatrixError::ByteLengthMismatch { expected, got: bytes.len() });
+        }
+        let values: Vec<f32> = bytes.chunks_exact(4).map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect();
+        Ok(Matrix::from_slice(&values, rows, cols))
+    }
+    ///The sum of every value of the Matrix
+    pub fn sum(&self) -> f32 {
+        self.data.iter().flatten().sum()
+    }
+    ///The largest value of the Matrix, negative infinity for an empty Matrix
+    pub fn max(&self) -> f32 {
+        self.data.iter().flatten().copied().fold(f32::NEG_INFINITY, f32::max)
+    }
+    ///Sums the Matrix along `axis`, axis 0 sums every column into a 1 x columns row and axis 1
+    ///sums every row into a rows x 1 column
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mat = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 9.0]]);
+    ///assert_eq!(mat.sum_axis(0).data, vec![vec![5.0, 7.0, 12.0]]);
+    ///assert_eq!(mat.sum_axis(1).data, vec![vec![6.0], vec![18.0]]);
+    ///assert_eq!(mat.mean_axis(0).data, vec![vec![2.5, 3.5, 6.0]]);
+    ///assert_eq!(mat.mean_axis(1).data, vec![vec![2.0], vec![6.0]]);
+    ///assert_eq!(mat.max_axis(0).data, vec![vec![4.0, 5.0, 9.0]]);
+    ///assert_eq!(mat.max_axis(1).data, vec![vec![3.0], vec![9.0]]);
+    ///assert_eq!((mat.sum(), mat.max()), (24.0, 9.0));
+    ///```
+    pub fn sum_axis(&self, axis: usize) -> Matrix {
+        self.reduce_axis(axis, &|values| values.iter().sum())
+    }
+    ///Averages the Matrix along `axis`, see sum_axis for how the axes are laid out
+    pub fn mean_axis(&self, axis: usize) -> Matrix {
+        self.reduce_axis(axis, &|values| values.iter().sum::<f32>() / values.len() as f32)
+    }
+    ///The largest values of the Matrix along `axis`, see sum_axis for how the axes are laid out
+    pub fn max_axis(&self, axis: usize) -> Matrix {
+        self.reduce_axis(axis, &|values| values.iter().copied().fold(f32::NEG_INFINITY, f32::max))
+    }
+    fn reduce_axis(&self, axis: usize, reduce: &dyn Fn(&[f32]) -> f32) -> Matrix {
+        match axis {
+            0 => Matrix::from_slice(&(0..self.columns).map(|col| reduce(&self.col(col))).collect::<Vec<f32>>(), 1, self.columns),
+            1 => Matrix::from_slice(&self.data.iter().map(|row| reduce(row)).collect::<Vec<f32>>(), self.rows, 1),
+            _ => panic!("Error attempting to reduce a matrix along axis {}, only axes 0 (columns) and 1 (rows) exist", axis),
+        }
+    }
+    ///Whether every value of the Matrix is finite, neither NaN nor infinite
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mut mat = Matrix::from(vec![vec![1.0, -2.0], vec![0.0, 1e30]]);
+    ///assert!(mat.is_finite());
+    ///
+    ///mat.set(1, 1, f32::NAN);
+    ///assert!(!mat.is_finite());
+    ///assert!(!Matrix::from(vec![vec![f32::INFINITY]]).is_finite());
+    ///```
+    pub fn is_finite(&self) -> bool {
+        self.data.iter().flatten().all(|value| value.is_finite())
+    }
+    ///The values of row `i`
+    pub fn row(&self, i: usize) -> &[f32] {
+        &self.data[i]
+    }
+    ///A copy of the values of column `j`, from the top row down
+    pub fn col(&self, j: usize) -> Vec<f32> {
+        self.data.iter().map(|row| row[j]).collect()
+    }
+    ///The value at row `i` and column `j`
+    pub fn get(&self, i: usize, j: usize) -> f32 {
+        self.data[i][j]
+    }
+    ///Replaces the value at row `i` and column `j`
+    pub fn set(&mut self, i: usize, j: usize, val: f32) {
+        self.data[i][j] = val;
+    }
+    ///Applies `function` to every value of the Matrix in place, the same as map without allocating
+    ///a new Matrix, for whenever the original values aren't needed afterwards
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mut mat = Matrix::from(vec![vec![1.0, -2.0], vec![3.0, 0.5]]);
+    ///let mapped = mat.map(&|x| x * 2.0);
+    ///mat.apply_inplace(&|x| x * 2.0);
+    ///assert_eq!(mat.data, vec![vec![2.0, -4.0], vec![6.0, 1.0]]);
+    ///assert_eq!(mat.data, mapped.data);
+    ///```
+    pub fn apply_inplace(&mut self, function: &dyn Fn(f32) -> f32) {
+        for value in self.data.iter_mut().flatten() {
+            *value = function(*value);
+        }
+    }
+    ///A copy of the Matrix with every value clamped to [`min`, `max`], see clip_inplace
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mat = Matrix::from(vec![vec![-5.0, 0.5], vec![2.0, f32::NAN]]);
+    ///let clipped = mat.clip(0.0, 1.0);
+    ///assert_eq!(clipped.data[0], vec![0.0, 0.5]);
+    ///assert_eq!(clipped.data[1][0], 1.0);
+    ///assert!(clipped.data[1][1].is_nan());
+    ///
+    ///let mut mat = mat;
+    ///mat.clip_inplace(-1.0, 1.0);
+    ///assert_eq!(mat.data[0], vec![-1.0, 0.5]);
+    ///```
+    pub fn clip(&self, min: f32, max: f32) -> Matrix {
+        let mut res = self.clone();
+        res.clip_inplace(min, max);
+        res
+    }
+    ///Clamps every value of the Matrix to [`min`, `max`] in place. NaNs are passed through
+    ///unchanged rather than mapped to a bound, so they still show up with is_finite
+    ///
+    ///Panics if `min` is greater than `max` or either is NaN
+    pub fn clip_inplace(&mut self, min: f32, max: f32) {
+        if min.is_nan() || max.is_nan() || min > max {
+            panic!("Error attempting to clip a matrix to an empty range \nMin: {}\nMax: {}", min, max);
+        }
+        self.apply_inplace(&|value| value.clamp(min, max));
+    }
     pub fn map(&mut self, function: &dyn Fn(f32) -> f32) -> Matrix{
         Matrix::from((self.data).clone()
                      .into_iter()
                      .map(|row| row
                           .into_iter()
-                          .map(|value| function(value))
+                          .map(function)
                           .collect())
                      .collect())
     }
-    pub fn transpose(&mut self) -> Matrix {
+    ///The determinant of a square Matrix, found through its LU decomposition. Panics if the Matrix
+    ///isn't square
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mat = Matrix::from(vec![vec![4.0, 3.0], vec![6.0, 3.0]]);
+    ///assert!((mat.determinant() + 6.0).abs() < 1e-5);
+    ///
+    ///let mat = Matrix::from(vec![vec![2.0, -3.0, 1.0], vec![2.0, 0.0, -1.0], vec![1.0, 4.0, 5.0]]);
+    ///assert!((mat.determinant() - 49.0).abs() < 1e-4);
+    ///
+    ///let singular = Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+    ///assert_eq!(singular.determinant(), 0.0);
+    ///```
+    pub fn determinant(&self) -> f32 {
+        match self.lu_decompose() {
+            Some((lu, _, sign)) => (sign * (0..lu.len()).map(|i| lu[i][i]).product::<f64>()) as f32,
+            None => 0.0
+        }
+    }
+    ///The inverse of a square Matrix, or None if the Matrix is singular. Solves against every
+    ///column of the identity with the Matrix's LU decomposition, panics if the Matrix isn't
+    ///square
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mat = Matrix::from(vec![vec![4.0, 7.0], vec![2.0, 6.0]]);
+    ///let inverse = mat.inverse().unwrap();
+    ///let expected = [[0.6, -0.7], [-0.2, 0.4]];
+    ///for i in 0..2 {
+    ///    for j in 0..2 {
+    ///        assert!((inverse.get(i, j) - expected[i][j]).abs() < 1e-5);
+    ///    }
+    ///}
+    ///
+    ///let mat = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 4.0], vec![5.0, 6.0, 0.0]]);
+    ///let identity = mat.inverse().unwrap() * &mat;
+    ///for i in 0..3 {
+    ///    for j in 0..3 {
+    ///        assert!((identity.get(i, j) - if i == j { 1.0 } else { 0.0 }).abs() < 1e-4);
+    ///    }
+    ///}
+    ///
+    ///let singular = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]]);
+    ///assert!(singular.inverse().is_none());
+    ///```
+    pub fn inverse(&self) -> Option<Matrix> {
+        let (lu, permutation, _) = self.lu_decompose()?;
+        let size = lu.len();
+        let mut res = Matrix::new_empty(size, size);
+        for col in 0..size {
+            //Forward substitution through L (with its implicit unit diagonal), then back through U
+            let mut solution: Vec<f64> = (0..size).map(|row| if permutation[row] == col { 1.0 } else { 0.0 }).collect();
+            for row in 0..size {
+                for k in 0..row {
+                    solution[row] -= lu[row][k] * solution[k];
+                }
+            }
+            for row in (0..size).rev() {
+                for k in row + 1..size {
+                    solution[row] -= lu[row][k] * solution[k];
+                }
+                solution[row] /= lu[row][row];
+            }
+            for (row, value) in solution.into_iter().enumerate() {
+                res.data[row][col] = value as f32;
+            }
+        }
+        Some(res)
+    }
+    //Doolittle LU decomposition with partial pivoting, done in f64 so the f32 results stay
+    //accurate. Returns L and U packed into one matrix, the row each row of it came from and the
+    //sign the row swaps flip the determinant by, or None if the Matrix is singular
+    fn lu_decompose(&self) -> Option<(Vec<Vec<f64>>, Vec<usize>, f64)> {
+        if self.rows != self.columns {
+            panic!("Error attempting to decompose a non-square matrix \nMatrix: {} x {}", self.rows, self.columns);
+        }
+        let size = self.rows;
+        let mut lu: Vec<Vec<f64>> = self.data.iter().map(|row| row.iter().map(|&val| val as f64).collect()).collect();
+        let mut permutation: Vec<usize> = (0..size).collect();
+        let mut sign = 1.0;
+
+        for col in 0..size {
+            let pivot = (col..size).max_by(|&a, &b| lu[a][col].abs().total_cmp(&lu[b][col].abs()))?;
+            if lu[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+            if pivot != col {
+                lu.swap(pivot, col);
+                permutation.swap(pivot, col);
+                sign = -sign;
+            }
+            for row in col + 1..size {
+                let factor = lu[row][col] / lu[col][col];
+                lu[row][col] = factor;
+                let pivot_row = lu[col].clone();
+                for (value, pivot_value) in lu[row].iter_mut().zip(pivot_row.iter()).skip(col + 1) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+        Some((lu, permutation, sign))
+    }
+    pub fn transpose(&self) -> Matrix {
         let mut res = Matrix::new_empty(self.columns, self.rows);
 
         for i in 0..self.rows{
@@ -242,6 +765,37 @@ impl Matrix{
         }
         res
     }
+    ///Transposes the Matrix in place. A square Matrix swaps its values across the diagonal
+    ///without allocating, any other shape falls back to transpose, so this is the cheaper choice
+    ///whenever the original Matrix isn't needed afterwards
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::matrix::Matrix;
+    ///
+    ///let mut square = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    ///square.transpose_in_place();
+    ///assert_eq!(square.data, vec![vec![1.0, 3.0], vec![2.0, 4.0]]);
+    ///
+    ///let mut wide = Matrix::from(vec![vec![1.0, 2.0, 3.0]]);
+    ///wide.transpose_in_place();
+    ///assert_eq!((wide.rows, wide.columns), (3, 1));
+    ///assert_eq!(wide.flatten(), vec![1.0, 2.0, 3.0]);
+    ///```
+    pub fn transpose_in_place(&mut self) {
+        if self.rows != self.columns {
+            *self = self.transpose();
+            return;
+        }
+        for i in 0..self.rows {
+            for j in i + 1..self.columns {
+                let value = self.data[i][j];
+                self.data[i][j] = self.data[j][i];
+                self.data[j][i] = value;
+            }
+        }
+    }
 }
 
 
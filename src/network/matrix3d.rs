@@ -1,9 +1,9 @@
-use rand::{thread_rng, Rng};
+use rand::Rng;
 use serde::{Serialize, Deserialize};
 
 use std::ops;
 
-use super::matrix::Matrix;
+use super::{matrix::Matrix, rng};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Matrix3D {
@@ -20,10 +20,10 @@ impl ops::Add<Vec<f32>> for Matrix3D {
             panic!("Vec of scalar values not same size as layers");
         }
         let mut res = Matrix3D::new_empty(self.rows, self.columns, self.layers);
-        for k in 0..self.layers{
+        for (k, scalar) in rhs.iter().enumerate(){
             for j in 0..self.rows{
                 for i in 0..self.columns{
-                    res.data[k][j][i] = self.data[k][j][i] + rhs[k];
+                    res.data[k][j][i] = self.data[k][j][i] + scalar;
                 }
             }
         }
@@ -37,15 +37,15 @@ impl Matrix3D{
     }
     pub fn new_random(rows: usize, cols: usize, layers: usize) -> Matrix3D {
         let mut res = Matrix3D::new_empty(rows, cols, layers);
-        let mut rng = thread_rng();
-
-        for z in 0..layers {
-            for y in 0..rows {
-                for x in 0..cols {
-                    res.data[z][y][x] = rng.gen::<f32>() * 2.0 - 1.0;
+        rng::with_rng(|rng| {
+            for z in 0..layers {
+                for y in 0..rows {
+                    for x in 0..cols {
+                        res.data[z][y][x] = rng.gen::<f32>() * 2.0 - 1.0;
+                    }
                 }
             }
-        }
+        });
         res
     }
     pub fn get_slice(&self, idx: usize) -> Matrix {
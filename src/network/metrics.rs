@@ -0,0 +1,56 @@
+//Scores for binary classifiers, every prediction is a score that counts as positive once it
+//reaches `threshold` and every label is positive when it's 0.5 or above
+
+///The fraction of samples scored as positive that really are positive, 0 when none are
+///
+///# Example
+///
+///```
+///use triton_grow::network::metrics::{precision, recall, f1_score};
+///
+///let scores = [0.9, 0.8, 0.3, 0.6, 0.1];
+///let labels = [1.0, 0.0, 1.0, 1.0, 0.0];
+///
+///assert!((precision(&scores, &labels, 0.5) - 2.0 / 3.0).abs() < 1e-6);
+///assert!((recall(&scores, &labels, 0.5) - 2.0 / 3.0).abs() < 1e-6);
+///assert!((f1_score(&scores, &labels, 0.5) - 2.0 / 3.0).abs() < 1e-6);
+///assert_eq!(recall(&scores, &labels, 0.2), 1.0);
+///assert_eq!(precision(&scores, &labels, 1.0), 0.0);
+///```
+pub fn precision(scores: &[f32], labels: &[f32], threshold: f32) -> f32 {
+    let (true_positives, false_positives, _) = counts(scores, labels, threshold);
+    ratio(true_positives, true_positives + false_positives)
+}
+
+///The fraction of positive samples that are scored as positive, 0 when there are none
+pub fn recall(scores: &[f32], labels: &[f32], threshold: f32) -> f32 {
+    let (true_positives, _, false_negatives) = counts(scores, labels, threshold);
+    ratio(true_positives, true_positives + false_negatives)
+}
+
+///The harmonic mean of precision and recall
+pub fn f1_score(scores: &[f32], labels: &[f32], threshold: f32) -> f32 {
+    let (true_positives, false_positives, false_negatives) = counts(scores, labels, threshold);
+    ratio(2 * true_positives, 2 * true_positives + false_positives + false_negatives)
+}
+
+//True positives, false positives and false negatives
+fn counts(scores: &[f32], labels: &[f32], threshold: f32) -> (usize, usize, usize) {
+    let mut res = (0, 0, 0);
+    for (score, label) in scores.iter().zip(labels.iter()) {
+        match (*score >= threshold, *label >= 0.5) {
+            (true, true) => res.0 += 1,
+            (true, false) => res.1 += 1,
+            (false, true) => res.2 += 1,
+            (false, false) => {},
+        }
+    }
+    res
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f32 {
+    if denominator == 0 {
+        return 0.0;
+    }
+    numerator as f32 / denominator as f32
+}
@@ -1,6 +1,24 @@
 pub mod matrix;
 pub mod matrix3d;
+#[allow(clippy::module_inception)]
 pub mod network;
 pub mod activations;
 pub mod input;
 pub mod layer;
+pub mod error;
+pub mod modes;
+pub mod loss;
+pub mod builder;
+pub mod schedule;
+pub mod optimizer;
+pub mod initializer;
+pub mod gradcheck;
+pub mod data;
+pub mod preprocess;
+pub mod ensemble;
+pub mod metrics;
+pub mod rng;
+pub mod tune;
+pub mod quantize;
+mod binary;
+mod onnx;
@@ -0,0 +1,21 @@
+use serde::{Serialize, Deserialize};
+
+///Whether a network is being trained or used for inference, layers such as Dropout behave
+///differently depending on the mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Mode{
+    #[default]
+    TRAIN,
+    EVAL
+}
+
+///What a network's outputs stand for, classifiers can have their outputs decoded into a class
+///label with Network::predict_class
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Task{
+    ///Outputs are raw values
+    #[default]
+    REGRESSION,
+    ///Outputs are scores for each class (or a single probability for binary classifiers)
+    CLASSIFICATION
+}
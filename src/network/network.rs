@@ -1,50 +1,598 @@
 use super::layer::layers::{Layer, LayerTypes};
 use super::matrix::Matrix;
+use super::activations::Activations;
 use super::input::Input;
+use super::error::{NetworkError, ExportError};
+use super::modes::{Mode, Task};
+use super::loss::{Loss, sparse_class};
+use super::schedule::Schedule;
+use super::optimizer::Optimizer;
+use super::binary;
+use super::onnx;
+use super::quantize::QuantizedNetwork;
+use super::rng;
+use super::metrics;
+use super::data::{argmax, smooth_labels};
 use serde::{Serialize, Deserialize};
+use rayon::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
 
-use serde_json::{to_string, from_str};
+use serde_json::{to_string, from_str, to_value, from_value};
 use std::{
+    collections::HashMap,
     fs::File,
     io::{Read,Write},
+    time::{Duration, Instant},
 };
 
-#[derive(Serialize, Deserialize)]
+//Added to the seed of a seeded network on every reset_weights, odd so resets never cycle back
+//to a seed they've already used
+const RESEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+///A neural network built from a queue of layers, see Network::new
+///
+///Cloning a network deep copies every layer (see Layer::clone_box), weights and optimizer state
+///included, so a clone can be trained or snapshotted without touching the original
+///
+///# Example
+///
+///```
+///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+///
+///let mut new_net = Network::new(2);
+///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+///new_net.compile();
+///
+///let mut snapshot = new_net.clone();
+///let before = snapshot.predict(vec![1.0, 0.0]).unwrap();
+///assert_eq!(new_net.predict(vec![1.0, 0.0]).unwrap(), before);
+///
+///new_net.fit(&[vec![1.0, 0.0]], &[vec![1.0]], 50).unwrap();
+///assert_ne!(new_net.predict(vec![1.0, 0.0]).unwrap(), before);
+///assert_eq!(snapshot.predict(vec![1.0, 0.0]).unwrap(), before);
+///```
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Network {
-    batch_size: usize,
     pub layer_sizes: Vec<usize>,
     pub loss: f32,
     loss_train: Vec<f32>,
     pub layers: Vec<Box<dyn Layer>>,
     uncompiled_layers: Vec<LayerTypes>,
+    #[serde(default)]
+    mode: Mode,
+    #[serde(default)]
+    loss_fn: Loss,
+    #[serde(default)]
+    schedule: Schedule,
+    #[serde(default)]
+    gradient_clip: Option<f32>,
+    #[serde(default)]
+    weight_decay: f32,
+    #[serde(default)]
+    task: Task,
+    #[serde(default)]
+    verbose: bool,
+    #[serde(default)]
+    iterations_per_epoch: Option<usize>,
+    #[serde(default)]
+    output_activation: Option<Activations>,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    momentum: Option<f32>,
+    #[serde(default)]
+    divergence_check: bool,
+    #[serde(default)]
+    track_layer_stats: bool,
+    #[serde(skip)]
+    layer_stats: Vec<LayerStats>,
+    #[serde(default)]
+    branches: Vec<(String, Network)>,
+    #[serde(default)]
+    layer_learning_rates: Vec<Option<f32>>,
+    #[serde(default)]
+    exits: Vec<(usize, Network)>,
+    #[serde(default)]
+    label_smoothing: f32,
+    #[serde(default)]
+    optimizer: Optimizer,
+}
+
+///The loss and accuracy of a network over a set of samples, see Network::evaluate
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EvalResult {
+    ///The mean loss of the samples according to the network's loss function
+    pub loss: f32,
+    ///The fraction of samples classified correctly, only meaningful for classifiers
+    pub accuracy: f32,
 }
 
-const ITERATIONS_PER_EPOCH: usize = 1000;
+///What happened during training, one entry per epoch, returned by fit and the other training
+///methods
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct History {
+    ///The mean training loss of every epoch
+    pub loss: Vec<f32>,
+    ///The loss on the validation set after every epoch, only measured by fit_with_validation
+    pub val_loss: Option<Vec<f32>>,
+    ///The fraction of training samples classified correctly in every epoch, empty unless the
+    ///network's task is Task::CLASSIFICATION
+    pub accuracy: Vec<f32>,
+}
+
+///The state of a single layer at the last update, see Network::layer_stats
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LayerStats {
+    ///The L2 norm of every trainable parameter of the layer
+    pub weight_norm: f32,
+    ///The L2 norm of the gradients the update was made from, after averaging over the batch
+    pub grad_norm: f32,
+    ///The mean of the layer's output for the last sample it saw
+    pub mean_activation: f32,
+}
 
 impl Network{
-    ///Creates a new neural network that is completely empty
+    ///Creates a new neural network without any layers, taking inputs of `input_size` values
     ///
     ///Example:
     ///```
-    ///let mut new_net = Network::new();
+    ///use triton_grow::network::network::Network;
+    ///
+    ///let mut new_net = Network::new(2);
+    ///assert_eq!(new_net.layer_sizes, vec![2]);
     ///```
-    pub fn new(batch_size: usize) -> Network{
+    pub fn new(input_size: usize) -> Network{
         Network{
-            batch_size,
-            layer_sizes: vec![],
+            layer_sizes: vec![input_size],
             loss: 1.0,
             layers: vec![],
             uncompiled_layers: vec![],
-            loss_train: vec![]
+            loss_train: vec![],
+            mode: Mode::TRAIN,
+            loss_fn: Loss::MSE,
+            schedule: Schedule::Constant,
+            gradient_clip: None,
+            weight_decay: 0.0,
+            task: Task::REGRESSION,
+            verbose: false,
+            iterations_per_epoch: None,
+            output_activation: None,
+            seed: None,
+            momentum: None,
+            divergence_check: false,
+            track_layer_stats: false,
+            layer_stats: vec![],
+            branches: vec![],
+            layer_learning_rates: vec![],
+            exits: vec![],
+            label_smoothing: 0.0,
+            optimizer: Optimizer::Adam,
+        }
+    }
+    ///Creates a new neural network like Network::new, whose layers are initialized from an RNG
+    ///seeded with `seed` when compiled, so two networks with the same seed and layers start out
+    ///with identical weights. Dropout layers draw their masks from an RNG seeded the same way, so
+    ///training them with fit_seeded is reproducible too
+    ///
+    ///Layers sized lazily by the first input they see (like CONV1D) initialize their weights
+    ///outside of compile and aren't covered by the seed
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let build = || {
+    ///    let mut net = Network::new_seeded(2, 42);
+    ///    net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.1));
+    ///    net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///    net.compile();
+    ///    net
+    ///};
+    ///
+    ///let (mut first, mut second) = (build(), build());
+    ///assert_eq!(first.predict(vec![0.3, 0.7]).unwrap(), second.predict(vec![0.3, 0.7]).unwrap());
+    ///```
+    pub fn new_seeded(input_size: usize, seed: u64) -> Network{
+        Network{
+            seed: Some(seed),
+            ..Network::new(input_size)
         }
-    } 
+    }
     pub fn get_layer_loss(&self) -> Vec<(f32, f32)> {
         let mut res: Vec<(f32, f32)> = vec![];
-        for i in 0..self.layers.len() - 1{
+        for i in 0..self.layers.len(){
             res.push(((i) as f32, self.layers[i].get_loss()));
         }
         res
     }
+    ///Switches the network between training and inference, layers like Dropout only apply
+    ///themselves while the network is in Mode::TRAIN (the default)
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, modes::Mode};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DROPOUT(0.5));
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///new_net.set_mode(Mode::EVAL);
+    ///let first = new_net.predict(vec![1.0, 0.0]).unwrap();
+    ///assert_eq!(first, new_net.predict(vec![1.0, 0.0]).unwrap());
+    ///```
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        for layer in self.layers.iter_mut() {
+            layer.set_mode(mode);
+        }
+        let heads = self.branches.iter_mut().map(|(_, branch)| branch).chain(self.exits.iter_mut().map(|(_, exit)| exit));
+        for head in heads {
+            head.set_mode(mode);
+        }
+    }
+    ///Sets what the network's outputs stand for, defaults to Task::REGRESSION
+    pub fn set_task(&mut self, task: Task) {
+        self.task = task;
+    }
+    ///Turns training output on or off, when verbose every epoch prints its number, loss and how
+    ///long it took, followed by a summary of the final loss once training is done. Networks are
+    ///quiet by default
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+    ///Turns on checking the loss and every layer's weights after each epoch of training, the fit
+    ///functions return NetworkError::DivergedAtEpoch as soon as any of them aren't finite instead
+    ///of training on with NaNs. Off by default, as it goes over every weight once per epoch
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, error::NetworkError};
+    ///
+    ///let mut new_net = Network::new(1);
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::LEAKYRELU(1.0), 1e30));
+    ///new_net.set_momentum(0.0);
+    ///new_net.set_divergence_check(true);
+    ///new_net.compile();
+    ///
+    ///let res = new_net.fit(&[vec![1e10]], &[vec![1.0]], 10);
+    ///assert!(matches!(res, Err(NetworkError::DivergedAtEpoch(_))));
+    ///```
+    pub fn set_divergence_check(&mut self, enabled: bool) {
+        self.divergence_check = enabled;
+    }
+    ///Turns on recording the weight norm, gradient norm and mean activation of every layer each
+    ///time the weights are updated, which shows where gradients vanish or explode. Off by default,
+    ///as it goes over every weight on every update
+    pub fn set_track_layer_stats(&mut self, enabled: bool) {
+        self.track_layer_stats = enabled;
+        if !enabled {
+            self.layer_stats = vec![];
+        }
+    }
+    ///The stats of every layer at the last update, one per compiled layer. Empty unless tracking
+    ///was turned on with set_track_layer_stats before training
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::RELU, 0.1));
+    ///new_net.add_layer(LayerTypes::DROPOUT(0.0));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///assert!(new_net.layer_stats().is_empty());
+    ///
+    ///new_net.set_track_layer_stats(true);
+    ///new_net.fit(&[vec![1.0, 0.5]], &[vec![1.0]], 3).unwrap();
+    ///
+    ///let stats = new_net.layer_stats();
+    ///assert_eq!(stats.len(), 3);
+    ///assert!(stats[0].weight_norm > 0.0 && stats[0].mean_activation >= 0.0);
+    ///assert_eq!(stats[1].weight_norm, 0.0);
+    ///assert!(stats[2].grad_norm > 0.0);
+    ///```
+    pub fn layer_stats(&self) -> Vec<LayerStats> {
+        self.layer_stats.clone()
+    }
+    ///Sets how many passes over the training data fit makes per epoch, every epoch is a single
+    ///pass by default. The loss of an epoch is the mean over all of its passes
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///new_net.set_verbose(true);
+    ///new_net.set_iterations_per_epoch(1);
+    ///
+    ///new_net.fit(&inputs, &outputs, 3).unwrap();
+    ///assert_eq!(new_net.get_loss_history().len(), 3);
+    ///```
+    pub fn set_iterations_per_epoch(&mut self, iterations: usize) {
+        self.iterations_per_epoch = Some(iterations.max(1));
+    }
+    ///Fails with NetworkError::DivergedAtEpoch if divergence checks are on and the loss of
+    ///`epoch` or any layer's weights aren't finite
+    fn check_divergence(&self, epoch: usize, loss: f32) -> Result<(), NetworkError> {
+        if !self.divergence_check {
+            return Ok(());
+        }
+        let diverged = !loss.is_finite() || self.layers.iter().any(|layer| !layer.parameters_finite());
+        if diverged {
+            return Err(NetworkError::DivergedAtEpoch(epoch + 1));
+        }
+        Ok(())
+    }
+    ///Prints an epoch's loss and duration when the network is verbose
+    fn log_epoch(&self, epoch: usize, loss: f32, elapsed: Duration) {
+        if self.verbose {
+            println!("Epoch {}: loss {:.4} ({:.2?})", epoch + 1, loss, elapsed);
+        }
+    }
+    ///Prints the final loss of the network and the error of every layer when the network is
+    ///verbose
+    fn log_trained(&self) {
+        if self.verbose {
+            println!("Trained to a loss of {:.2}%", self.loss * 100.0);
+            for i in 0..self.layers.len() {
+                println!("Error on layer {}: +/- {:.2}", i + 1, self.layers[i].get_loss());
+            }
+        }
+    }
+    ///Sets the loss function the network is trained against, defaults to Loss::MSE
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, loss::Loss};
+    ///
+    ///let mut new_net = Network::new_seeded(2, 1);
+    ///new_net.add_layer(LayerTypes::DENSE(8, Activations::TANH, 0.05));
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SOFTMAX, 0.05));
+    ///new_net.compile();
+    ///new_net.set_loss(Loss::CROSSENTROPY);
+    ///
+    ///let inputs = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+    ///let outputs = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+    ///let history = new_net.fit_seeded(&inputs, &outputs, 300, 1).unwrap();
+    ///assert!(history.loss[299] < 0.01);
+    ///```
+    pub fn set_loss(&mut self, loss: Loss) {
+        self.loss_fn = loss;
+    }
+    ///Trains against smoothed targets whenever the loss is Loss::CROSSENTROPY or
+    ///Loss::SPARSECROSSENTROPY, the true class counts as `1 - epsilon` and the rest share `epsilon`
+    ///(see data::smooth_labels). Keeps a classifier from growing overconfident, 0 (the default)
+    ///turns smoothing off
+    ///
+    ///Only the errors trained on are smoothed, losses and accuracies are still measured against
+    ///the targets as given
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, loss::Loss, modes::Task};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+    ///
+    ///let mut hard = Network::new_seeded(2, 3);
+    ///hard.add_layer(LayerTypes::DENSE(8, Activations::TANH, 0.1));
+    ///hard.add_layer(LayerTypes::DENSE(3, Activations::SOFTMAX, 0.1));
+    ///hard.set_loss(Loss::CROSSENTROPY);
+    ///hard.set_task(Task::CLASSIFICATION);
+    ///hard.compile();
+    ///let mut smoothed = hard.clone();
+    ///smoothed.set_label_smoothing(0.2);
+    ///
+    ///hard.fit_seeded(&inputs, &outputs, 500, 3).unwrap();
+    ///smoothed.fit_seeded(&inputs, &outputs, 500, 3).unwrap();
+    ///assert_eq!(smoothed.accuracy(&inputs, &outputs), hard.accuracy(&inputs, &outputs));
+    ///
+    ///for input in inputs.iter() {
+    ///    let confident = hard.predict(input.clone()).unwrap().into_iter().fold(0.0, f32::max);
+    ///    let calibrated = smoothed.predict(input.clone()).unwrap().into_iter().fold(0.0, f32::max);
+    ///    assert!(calibrated < confident);
+    ///    assert!((calibrated - 0.8).abs() < 0.1);
+    ///}
+    ///```
+    pub fn set_label_smoothing(&mut self, epsilon: f32) {
+        self.label_smoothing = epsilon;
+    }
+    ///Sets how the learning rate of every layer changes as the network trains, defaults to
+    ///Schedule::Constant
+    ///
+    ///The rate a layer was declared with is scaled by the schedule at the start of every epoch
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, schedule::Schedule};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///new_net.set_lr_schedule(Schedule::ExponentialDecay { gamma: 0.9 });
+    ///new_net.fit_batched(&inputs, &outputs, 5, 4).unwrap();
+    ///```
+    pub fn set_lr_schedule(&mut self, schedule: Schedule) {
+        self.schedule = schedule;
+    }
+    ///Pushes the scheduled learning rate for `epoch` into every layer that trains
+    fn schedule_learning_rates(&mut self, epoch: usize) {
+        for index in 0..self.layers.len() {
+            if let Some(base) = self.layer_lr(index) {
+                self.layers[index].set_learning_rate(self.schedule.learning_rate(base, epoch));
+            }
+        }
+    }
+    ///Rescales the weight and bias gradients every layer computes for a sample so their combined L2
+    ///norm never exceeds `max_norm`, keeping exploding gradients from blowing the weights up. Every
+    ///sample is clipped before it's accumulated, so the averaged gradient of a batch stays within
+    ///`max_norm` too
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let l2_norm = |values: Vec<f32>| values.iter().map(|value| value * value).sum::<f32>().sqrt();
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![500.0, -800.0], vec![1000.0, 250.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new_seeded(2, 1);
+    ///new_net.add_layer(LayerTypes::DENSE(8, Activations::LEAKYRELU(0.1), 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::LEAKYRELU(0.1), 0.1));
+    ///new_net.compile();
+    ///let mut unclipped = new_net.clone();
+    ///new_net.set_gradient_clip(1.0);
+    ///
+    ///unclipped.accumulate_gradients(&inputs[0], &outputs[0]).unwrap();
+    ///new_net.accumulate_gradients(&inputs[0], &outputs[0]).unwrap();
+    ///for (layer, unclipped_layer) in new_net.layers.iter().zip(unclipped.layers.iter()) {
+    ///    assert!(l2_norm(unclipped_layer.parameter_gradients()) > 1.0);
+    ///    assert!(l2_norm(layer.parameter_gradients()) <= 1.0 + 1e-4);
+    ///}
+    /////The average of a batch of clipped samples can't exceed the clip either
+    ///new_net.accumulate_gradients(&inputs[1], &outputs[1]).unwrap();
+    ///for layer in new_net.layers.iter() {
+    ///    assert!(l2_norm(layer.parameter_gradients()) / 2.0 <= 1.0 + 1e-4);
+    ///}
+    ///new_net.apply_accumulated(0.5);
+    ///
+    ///new_net.fit_batched(&inputs, &outputs, 20, 1).unwrap();
+    ///for output in new_net.predict_batch(&inputs).unwrap() {
+    ///    assert!(output[0].is_finite());
+    ///}
+    ///
+    ///new_net.clear_gradient_clip();
+    ///```
+    pub fn set_gradient_clip(&mut self, max_norm: f32) {
+        self.gradient_clip = Some(max_norm);
+        for layer in self.layers.iter_mut() {
+            layer.set_gradient_clip(self.gradient_clip);
+        }
+    }
+    ///Turns gradient clipping set with set_gradient_clip back off for every layer
+    pub fn clear_gradient_clip(&mut self) {
+        self.gradient_clip = None;
+        for layer in self.layers.iter_mut() {
+            layer.set_gradient_clip(None);
+        }
+    }
+    ///Turns on L2 regularization, every layer's weight gradient has `lambda` times its weights
+    ///taken away during back propegation so large weights are penalized. A `lambda` of 0 turns it
+    ///back off
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{layer::{layers::Layer, dense::Dense}, activations::Activations, input::Input, matrix::Matrix};
+    ///
+    ///let mut plain = Dense::new(3, 1, Activations::TANH, 0.05);
+    ///let mut decayed = Dense::new(3, 1, Activations::TANH, 0.05);
+    ///decayed.weights = plain.weights.clone();
+    ///decayed.biases = plain.biases.clone();
+    ///decayed.set_weight_decay(0.5);
+    ///
+    ///for i in 0..300 {
+    ///    let input = vec![(i % 7) as f32 / 7.0, (i % 3) as f32, ((i * 13) % 5) as f32 / 5.0];
+    ///    let noise = ((i * 31) % 11) as f32 / 55.0 - 0.1;
+    ///    let target = (2.0 * input[0] - 3.0 * input[2]).tanh() + noise;
+    ///    for layer in [&mut plain, &mut decayed] {
+    ///        let output = layer.forward(&input).to_param();
+    ///        let errors: Box<dyn Input> = Box::new(vec![vec![target - output[0]]]);
+    ///        layer.backward(layer.update_gradient(), errors, Box::new(Matrix::from(vec![input.clone()]).transpose()));
+    ///        layer.apply_gradients(1.0);
+    ///    }
+    ///}
+    ///assert!(decayed.weights.l2_norm() < plain.weights.l2_norm());
+    ///```
+    pub fn set_weight_decay(&mut self, lambda: f32) {
+        self.weight_decay = lambda;
+        for layer in self.layers.iter_mut() {
+            layer.set_weight_decay(lambda);
+        }
+    }
+    ///Trains every Dense layer with SGD and a momentum term instead of Adam, each layer keeps a
+    ///velocity of its weights and biases that's updated as v = momentum * v - lr * gradient before
+    ///moving the weights by v. A momentum of 0 is plain SGD
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]];
+    ///let outputs = vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]];
+    ///
+    ///let train = |momentum: f32| {
+    ///    let mut net = Network::new_seeded(2, 3);
+    ///    net.add_layer(LayerTypes::DENSE(4, Activations::SIGMOID, 0.5));
+    ///    net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.5));
+    ///    net.set_momentum(momentum);
+    ///    net.compile();
+    ///    net.fit_seeded(&inputs, &outputs, 500, 0).unwrap();
+    ///    net.loss
+    ///};
+    ///
+    ///assert!(train(0.9) < train(0.0));
+    ///```
+    pub fn set_momentum(&mut self, momentum: f32) {
+        self.momentum = Some(momentum);
+        for layer in self.layers.iter_mut() {
+            layer.set_momentum(self.momentum);
+        }
+    }
+    ///Sets the adaptive optimizer every Dense layer is trained with, defaults to Optimizer::Adam
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, optimizer::Optimizer};
+    ///
+    ///let inputs = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]];
+    ///let outputs = vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new_seeded(2, 3);
+    ///new_net.add_layer(LayerTypes::DENSE(8, Activations::TANH, 0.05));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.05));
+    ///new_net.set_optimizer(Optimizer::AdamW { weight_decay: 0.01 });
+    ///new_net.compile();
+    ///
+    ///new_net.fit_seeded(&inputs, &outputs, 500, 3).unwrap();
+    ///for (input, output) in inputs.iter().zip(outputs.iter()) {
+    ///    assert!((new_net.predict(input.clone()).unwrap()[0] - output[0]).abs() < 0.2);
+    ///}
+    ///```
+    pub fn set_optimizer(&mut self, optimizer: Optimizer) {
+        self.optimizer = optimizer;
+        for layer in self.layers.iter_mut() {
+            layer.set_optimizer(optimizer);
+        }
+    }
     pub fn get_loss_history(&self) -> Vec<f32> {
         self.loss_train.clone()
     }
@@ -52,170 +600,1933 @@ impl Network{
     ///
     ///# Arguments
     ///* `layer` - An enum depicting the options available from the Layers that exist(Dense,
-    ///Convolutional, etc)
+    ///  Convolutional, etc)
     ///
     ///# Example
     ///
     ///```
-    ///let mut new_net = Network::new();
-    ///new_new.add_layer(LayerTypes::Dense(4, Activations::SIGMOID, 0.01));
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::SIGMOID, 0.01));
     ///```
     ///Adds a new Dense layer of 4 nodes with the sigmoid activation and a learning rate of 0.01
     pub fn add_layer(&mut self, layer: LayerTypes){
         self.layer_sizes.push(layer.get_size());
         self.uncompiled_layers.push(layer);
     }
+    ///Adds every layer of `layers` to the queue in order, exactly as calling add_layer on each
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let blocks: Vec<LayerTypes> = (0..3).flat_map(|_| vec![LayerTypes::DENSE(8, Activations::RELU, 0.01), LayerTypes::DROPOUT(0.1)]).collect();
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layers(blocks);
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.01));
+    ///assert_eq!(new_net.layer_sizes, vec![2, 8, 0, 8, 0, 8, 0, 1]);
+    ///
+    ///new_net.compile();
+    ///assert_eq!(new_net.layers.len(), 7);
+    ///```
+    pub fn add_layers(&mut self, layers: Vec<LayerTypes>) {
+        for layer in layers {
+            self.add_layer(layer);
+        }
+    }
+    ///Adds an output head named `branch_id` on top of the network's layers, which then act as a
+    ///shared trunk. The branch is its own Network taking the trunk's output as input, so it has
+    ///its own loss, task and training settings (see Network::branch_mut) and is compiled along
+    ///with the trunk. Adding a branch again under the same id replaces it
+    ///
+    ///Branches are run by predict_heads and trained by fit_heads, predict and fit keep working
+    ///on the trunk alone
+    pub fn add_branch(&mut self, branch_id: &str, layers: Vec<LayerTypes>) {
+        //The input size is only a placeholder until compile knows the trunk's output size
+        let mut branch = Network::new(self.layer_sizes[self.layer_sizes.len()-1]);
+        branch.mode = self.mode;
+        for layer in layers {
+            branch.add_layer(layer);
+        }
+        self.branches.retain(|(id, _)| id != branch_id);
+        self.branches.push((branch_id.to_string(), branch));
+    }
+    ///The branch named `branch_id`, for setting its loss, task or anything else Network allows.
+    ///None if no branch has that id
+    pub fn branch_mut(&mut self, branch_id: &str) -> Option<&mut Network> {
+        self.branches.iter_mut().find(|(id, _)| id == branch_id).map(|(_, branch)| branch)
+    }
+    ///Attaches a small auxiliary classifier after the layer at `after_layer`, which
+    ///predict_with_early_exit consults to stop early once it's confident. Like a branch it's its
+    ///own Network, compiled along with the layers and trained by fit_exits
+    ///
+    ///Returns NetworkError::InvalidLayer if no layer has been added at `after_layer`
+    pub fn add_exit(&mut self, after_layer: usize, layers: Vec<LayerTypes>) -> Result<(), NetworkError> {
+        if after_layer >= self.uncompiled_layers.len() {
+            return Err(NetworkError::InvalidLayer { index: after_layer, reason: format!("the network only has {} layers", self.uncompiled_layers.len()) });
+        }
+        //The input size is only a placeholder until compile knows the layer's output size
+        let mut exit = Network::new(self.layer_sizes[after_layer + 1]);
+        exit.mode = self.mode;
+        exit.task = Task::CLASSIFICATION;
+        for layer in layers {
+            exit.add_layer(layer);
+        }
+        self.exits.push((after_layer, exit));
+        self.exits.sort_by_key(|(after_layer, _)| *after_layer);
+        Ok(())
+    }
     ///Compiles a network by constructing each of its layers accordingly
-    ///Must be done after all layers are added as the sizes of layer rows depends on the columns of
-    ///the next layer
+    ///Must be done after all layers are added as the size of each layer's input depends on the
+    ///output of the layer before it (or the network's input size for the first layer)
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///
+    ///new_net.compile();
+    ///assert_eq!(new_net.layers.len(), 2);
+    ///assert_eq!(new_net.layers[0].shape(), (3, 2, 0));
+    ///assert_eq!(new_net.layers[1].shape(), (1, 3, 0));
+    ///```
     pub fn compile(&mut self){
-        for i in 0..self.uncompiled_layers.len() - 1 {
-            let layer = self.uncompiled_layers[i].to_layer(self.layer_sizes[i+1]);
+        let mut prev_size = self.layer_sizes[0];
+        let mut output_sizes = vec![];
+        for (index, layer_type) in self.uncompiled_layers.iter().enumerate() {
+            let mut layer = match self.seed {
+                //Every layer gets its own seed so adding a layer doesn't shift the ones before it
+                Some(seed) => rng::with_seed(seed.wrapping_add(index as u64), || layer_type.to_layer(prev_size)),
+                None => layer_type.to_layer(prev_size),
+            };
+            layer.set_mode(self.mode);
+            layer.set_gradient_clip(self.gradient_clip);
+            layer.set_weight_decay(self.weight_decay);
+            layer.set_momentum(self.momentum);
+            layer.set_optimizer(self.optimizer);
             self.layers.push(layer);
+            prev_size = layer_type.output_size(prev_size);
+            output_sizes.push(prev_size);
+        }
+        if let (Some(activation), Some(output)) = (self.output_activation, self.layers.last_mut()) {
+            output.set_activation(activation);
+        }
+        for (index, (_, branch)) in self.branches.iter_mut().enumerate() {
+            branch.layer_sizes[0] = prev_size;
+            if let (Some(seed), None) = (self.seed, branch.seed) {
+                branch.seed = Some(seed.wrapping_add(((index + 1) as u64) << 32));
+            }
+            branch.compile();
+        }
+        for (index, (after_layer, exit)) in self.exits.iter_mut().enumerate() {
+            exit.layer_sizes[0] = output_sizes[*after_layer];
+            if let (Some(seed), None) = (self.seed, exit.seed) {
+                exit.seed = Some(seed.wrapping_add(((index + 1) as u64) << 48));
+            }
+            exit.compile();
         }
         //println!("{:?}", self.layer_sizes);
 
     }
-    pub fn predict(&mut self, input: Vec<f32>) -> Vec<f32>{
-        let in_box: Box<dyn Input> = Box::new(input);
-        self.feed_forward(&in_box)
+    ///Clears the state layers carry from one input to the next, call it between sequences when
+    ///the network has recurrent layers
+    pub fn reset_state(&mut self) {
+        for layer in self.layers.iter_mut() {
+            layer.reset_state();
+        }
     }
-    ///Travels through a neural network's abstracted Layers and returns the resultant vector at the
-    ///end
+    ///Throws away everything the network has learned by constructing every layer (branches and
+    ///exits included) again from scratch, with freshly initialized weights, biases and optimizer
+    ///state
     ///
-    ///# Arguments
-    ///* `input_obj` - Any structure that implements the Input trait to act as an input to the data
-    ///# Returns
-    ///A vector at the end of the feed forward
+    ///A seeded network moves on to a new seed with every reset, so its weights differ from the
+    ///ones before while the same seed still always resets to the same weights. See set_seed to
+    ///start over from a chosen seed instead
     ///
-    ///# Examples
+    ///# Example
     ///
     ///```
-    ///let new_net = Network::New();
-    ///new_new.add_layer(LayerTypes::Dense(2, Activations::SIGMOID, 0.01));
-    ///new_new.add_layer(LayerTypes::Dense(3, Activations::SIGMOID, 0.01));
-    ///new_new.add_layer(LayerTypes::Dense(4, Activations::SIGMOID, 0.01));
-    ///new_new.add_layer(LayerTypes::Dense(2, Activations::TANH, 0.01));
-    ///new_new.add_layer(LayerTypes::Dense(1, Activations::SIGMOID, 0.01));
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
     ///
-    ///new_net.compile()
+    ///let build = || {
+    ///    let mut net = Network::new_seeded(2, 9);
+    ///    net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///    net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///    net.compile();
+    ///    net
+    ///};
     ///
-    ///let res = new_net.feed_forward(vec![1.0, 0.54]);
+    ///let (mut first, mut second) = (build(), build());
+    ///let before = first.layers[0].parameters();
+    ///first.reset_weights();
+    ///second.reset_weights();
+    ///assert_eq!(first.layers.len(), 2);
+    ///assert_ne!(first.layers[0].parameters(), before);
+    ///assert_eq!(first.layers[0].parameters(), second.layers[0].parameters());
     ///```
-    fn feed_forward(&mut self, input_obj: &Box<dyn Input>) -> Vec<f32> {
-
-        if input_obj.shape().0 != self.layers[0].shape().1{
-            panic!("Input shape does not match input layer shape \nInput: {:?}\nInput Layer:{:?}", input_obj.shape(), self.layers[0].shape());
-        }
-        
-        let mut data_at: Box<dyn Input> = Box::new(input_obj.to_param());
-        for i in 0..self.layers.len(){
-            data_at = self.layers[i].forward(&data_at);
+    pub fn reset_weights(&mut self) {
+        self.seed = self.seed.map(|seed| seed.wrapping_add(RESEED));
+        for net in self.branches.iter_mut().map(|(_, branch)| branch).chain(self.exits.iter_mut().map(|(_, exit)| exit)) {
+            net.layers = vec![];
+            net.seed = net.seed.map(|seed| seed.wrapping_add(RESEED));
         }
-        data_at.to_param().to_owned()
+        self.layers = vec![];
+        self.compile();
+    }
+    ///Seeds the RNG the layers are initialized from, taking effect the next time the network is
+    ///compiled (reset_weights moves on from it to a new seed), see Network::new_seeded
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
     }
-    ///Travels backwards through a neural network and updates weights and biases accordingly
+    ///The seed the layers are initialized from, None when the network isn't seeded
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+    ///Overrides the activation of just the output layer, keeping whatever the hidden layers use.
+    ///The override survives reset_weights, and LEAKYRELU(1.0) makes for a linear output when
+    ///regressing targets of any sign
     ///
-    ///The backward behavior is different depending on the layer type, and therefore the weight and
-    ///bias updating is different as well
+    ///Returns NetworkError::EmptyNetwork if the network hasn't been compiled yet and
+    ///NetworkError::InvalidOutputLayer if the output layer has no activation to replace
     ///
-    ///When constructing a neural network, be cautious that your layers behave well with each other
-    fn back_propegate(&mut self, outputs: Vec<f32>, target_obj: &Box<dyn Input>) {
-        let parsed = Matrix::from(outputs.to_param_2d()).transpose();
-        
-        if let None = self.layers[self.layers.len()-1].get_activation() {
-            panic!("Output layer is not a dense layer");
-        }
-        
-        let mut gradients: Box<dyn Input>;
-        let mut errors: Box<dyn Input> = Box::new(Matrix::from(target_obj.to_param_2d()) - &parsed);
-
-        for i in (0..self.layers.len() - 1).rev() {
-            gradients = self.layers[i + 1].update_gradient();
-            let data_box: Box<dyn Input> = self.layers[i].get_data();
-            errors = self.layers[i+1].backward(gradients, errors, data_box);
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(1);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::RELU, 0.01));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::RELU, 0.01));
+    ///new_net.compile();
+    ///new_net.set_output_activation(Activations::LEAKYRELU(1.0)).unwrap();
+    ///
+    ///let inputs: Vec<Vec<f32>> = (0..8).map(|i| vec![i as f32 / 8.0]).collect();
+    ///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| vec![-1.0 - input[0]]).collect();
+    ///new_net.fit_batched(&inputs, &outputs, 300, 1).unwrap();
+    ///assert!(new_net.predict(vec![0.5]).unwrap()[0] < 0.0);
+    ///
+    ///new_net.reset_weights();
+    ///assert!(matches!(new_net.layers[1].get_activation(), Some(Activations::LEAKYRELU(_))));
+    ///```
+    pub fn set_output_activation(&mut self, activation: Activations) -> Result<(), NetworkError> {
+        let output = self.layers.last_mut().ok_or(NetworkError::EmptyNetwork)?;
+        if output.get_activation().is_none() {
+            return Err(NetworkError::InvalidOutputLayer);
         }
+        output.set_activation(activation);
+        self.output_activation = Some(activation);
+        Ok(())
     }
-    ///Trains a neural network by iteratively feeding forward a series of inputs and then doing
-    ///back propegation based on the outputs supplied
+    ///Freezes the compiled layer at `index`, errors still flow back through it during back
+    ///propegation but its weights and biases stay exactly as they are. Freezing the lower layers
+    ///of a pretrained network fine-tunes only the layers on top
     ///
-    ///# Arguments
-    ///* `train_in` - A vector of objects that implement the Input trait, used as the training
-    ///input
-    ///* `train_out` - A vector of objects that implement the Input trait, used as the results
-    ///compared to what is actually derived during back propegation
-    ///* `epochs` - How many epochs you want your model training for
+    ///# Example
     ///
-    pub fn fit(&mut self, train_in: &Vec<Vec<f32>>, train_out: &Vec<Vec<f32>>, epochs: usize) {
-        self.loss_train = vec![];
-
-        let mut loss: f32;
-        let num_batches = train_in.len() / self.batch_size;
-
-        let iteration_scale_factor = ITERATIONS_PER_EPOCH / train_in.len();
-        let iterations_per_epoch: usize = (iteration_scale_factor as f32 * 25.0).ceil() as usize;
-        println!("{}", iterations_per_epoch);
-
-        for _ in 0..epochs {
-            loss = 0.0;
-            for _ in 0..iterations_per_epoch {
-                for batch_index in 0..num_batches {
-                    let start = batch_index * self.batch_size;
-                    let end = start + self.batch_size;
-                    let end = end.min(train_in.len()); // Ensure 'end' doesn't go out of bounds
-
-                    let mut batch_loss: f32 = 0.0;
-                    for input_index in start..end {
-                        let mut loss_on_input: f32 = 0.0;
-                        let input: Box<dyn Input> = Box::new(train_in[input_index].clone());
-                        let output: Box<dyn Input> = Box::new(train_out[input_index].clone());
-                        let outputs = self.feed_forward(&input);
-                        self.back_propegate(outputs.clone(), &output);
-
-                        for i in 0..outputs.len() {
-                            loss_on_input += (outputs[i] - train_out[input_index].to_param()[i]).powi(2);
-                        }
-                        batch_loss += loss_on_input / outputs.len() as f32;
-                    }
-                    loss += batch_loss / self.batch_size as f32;
-                }
-            }
-            self.loss_train.push(loss / (ITERATIONS_PER_EPOCH * num_batches) as f32);
-        }
-
-        self.loss = self.loss_train[self.loss_train.len() - 1];
-        println!("Trained to a loss of {:.2}%", self.loss * 100.0);
-        for i in 0..self.layers.len() - 1 {
-            println!("Error on layer {}: +/- {:.2}", i + 1, self.layers[i].get_loss());
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///new_net.freeze_layer(0).unwrap();
+    ///
+    ///let frozen = new_net.layers[0].parameters();
+    ///let top = new_net.layers[1].parameters();
+    ///new_net.fit_batched(&inputs, &outputs, 5, 1).unwrap();
+    ///assert_eq!(new_net.layers[0].parameters(), frozen);
+    ///assert_ne!(new_net.layers[1].parameters(), top);
+    ///
+    ///assert!(new_net.freeze_layer(2).is_err());
+    ///```
+    pub fn freeze_layer(&mut self, index: usize) -> Result<(), NetworkError> {
+        self.set_layer_trainable(index, false)
+    }
+    ///Lets a layer frozen by freeze_layer train again
+    pub fn unfreeze_layer(&mut self, index: usize) -> Result<(), NetworkError> {
+        self.set_layer_trainable(index, true)
+    }
+    fn set_layer_trainable(&mut self, index: usize, trainable: bool) -> Result<(), NetworkError> {
+        match self.layers.get_mut(index) {
+            Some(layer) => {
+                layer.set_trainable(trainable);
+                Ok(())
+            },
+            None => Err(NetworkError::InvalidLayer { index, reason: format!("the network only has {} compiled layers", self.layers.len()) }),
         }
     }
-
-    pub fn save(&self, path: &str) {
-        let mut file = File::create(path).expect("Unable to hit save file :(");
-        let file_ser = to_string(self).expect("Unable to serialize network :(((");
-        file.write_all(file_ser.to_string().as_bytes()).expect("Write failed :(");
+    ///The learning rate the layer at `index` trains with before the schedule is applied, the one
+    ///it was added with unless set_layer_lr changed it. None for layers without weights (or
+    ///without a layer at `index`)
+    pub fn layer_lr(&self, index: usize) -> Option<f32> {
+        let base = self.uncompiled_layers.get(index)?.learning_rate()?;
+        Some(self.layer_learning_rates.get(index).copied().flatten().unwrap_or(base))
     }
-    pub fn load(path: &str) -> Network{
-        let mut buffer = String::new();
-        let mut file = File::open(path).expect("Unable to read file :(");
-
-        file.read_to_string(&mut buffer).expect("Unable to read file but even sadder :(");
-
-        let net: Network = from_str(&buffer).expect("Json was not formatted well >:(");
-        net
+    ///Changes the learning rate of the compiled layer at `index` to `lr`, the Network's
+    ///learning rate schedule then scales it like any other
+    ///
+    ///Returns NetworkError::InvalidLayer if there is no compiled layer at `index` or the layer has
+    ///no weights to learn
+    pub fn set_layer_lr(&mut self, index: usize, lr: f32) -> Result<(), NetworkError> {
+        if index >= self.layers.len() {
+            return Err(NetworkError::InvalidLayer { index, reason: format!("the network only has {} compiled layers", self.layers.len()) });
+        }
+        if self.layer_lr(index).is_none() {
+            return Err(NetworkError::InvalidLayer { index, reason: "the layer has no learning rate".to_string() });
+        }
+        self.store_layer_lr(index, lr);
+        Ok(())
     }
-
-    pub fn fit_to_loss(&mut self, train_in: Vec<Vec<f32>>, train_out: Vec<Vec<f32>>, desired_loss: f32, steps_per: usize) -> usize{
-        let mut epochs_total = 0;
-        while self.loss > desired_loss{
-            self.fit(&train_in, &train_out, steps_per);
-            epochs_total += steps_per;
-            
+    fn store_layer_lr(&mut self, index: usize, lr: f32) {
+        if self.layer_learning_rates.len() <= index {
+            self.layer_learning_rates.resize(index + 1, None);
         }
-        println!("Trained to a loss of {:.2}%", self.loss * 100.0);
-        for i in 0..self.layers.len()-1{
-            println!("Error on layer {}: +/- {:.2}", i+1, self.layers[i].get_loss());
+        self.layer_learning_rates[index] = Some(lr);
+        self.layers[index].set_learning_rate(lr);
+    }
+    ///Sets discriminative learning rates, interpolating linearly from `start_lr` at the first
+    ///layer with weights to `end_lr` at the last. Layers without weights are skipped, and a
+    ///network with a single such layer gets `start_lr`
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::RELU, 0.1));
+    ///new_net.add_layer(LayerTypes::DROPOUT(0.2));
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::RELU, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///new_net.set_lr_range(0.001, 0.01);
+    ///assert_eq!(new_net.layer_lr(0), Some(0.001));
+    ///assert_eq!(new_net.layer_lr(1), None);
+    ///assert!((new_net.layer_lr(2).unwrap() - 0.0055).abs() < 1e-7);
+    ///assert_eq!(new_net.layer_lr(3), Some(0.01));
+    ///
+    ///new_net.set_layer_lr(3, 0.5).unwrap();
+    ///assert_eq!(new_net.layer_lr(3), Some(0.5));
+    ///assert!(new_net.set_layer_lr(1, 0.5).is_err());
+    ///assert!(new_net.set_layer_lr(4, 0.5).is_err());
+    ///```
+    pub fn set_lr_range(&mut self, start_lr: f32, end_lr: f32) {
+        let trainable: Vec<usize> = (0..self.layers.len()).filter(|&index| self.layer_lr(index).is_some()).collect();
+        let steps = trainable.len().saturating_sub(1).max(1) as f32;
+        for (position, index) in trainable.into_iter().enumerate() {
+            self.store_layer_lr(index, start_lr + (end_lr - start_lr) * position as f32 / steps);
         }
-        epochs_total
+    }
+    ///Prints every compiled layer's type, shape, activation and number of trainable parameters,
+    ///followed by the total parameters of the network
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::RELU, 0.1));
+    ///new_net.add_layer(LayerTypes::DROPOUT(0.2));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///new_net.summary();
+    ///```
+    ///```text
+    ///Layer            Shape               Activation        Parameters
+    ///=================================================================
+    ///Dense            (3, 2, 0)           RELU                       9
+    ///Dropout          (0, 0, 0)           -                          0
+    ///Dense            (1, 3, 0)           SIGMOID                    4
+    ///=================================================================
+    ///Total trainable parameters: 13
+    ///```
+    pub fn summary(&self) {
+        println!("{:<16} {:<19} {:<17} {:>10}", "Layer", "Shape", "Activation", "Parameters");
+        println!("{}", "=".repeat(65));
+        for layer in self.layers.iter() {
+            let activation = layer.get_activation().map_or(String::from("-"), |activation| format!("{:?}", activation));
+            println!("{:<16} {:<19} {:<17} {:>10}", layer.typetag_name(), format!("{:?}", layer.shape()), activation, layer.parameter_count());
+        }
+        println!("{}", "=".repeat(65));
+        println!("Total trainable parameters: {}", self.num_parameters());
+    }
+    ///The number of trainable parameters (weights and biases) across every layer of the network,
+    ///branches and exits not included
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    /////2 * 3 weights + 3 biases, then 3 * 1 weights + 1 bias
+    ///assert_eq!(new_net.num_parameters(), 13);
+    ///```
+    pub fn num_parameters(&self) -> usize {
+        self.layers.iter().map(|layer| layer.parameter_count()).sum()
+    }
+    ///Runs a single input through the network, returning the output layer's values or a
+    ///NetworkError if the input does not fit the input layer
+    pub fn predict(&mut self, input: Vec<f32>) -> Result<Vec<f32>, NetworkError>{
+        self.feed_forward(&input)
+    }
+    ///Runs a single input through the trunk and then every branch added with add_branch,
+    ///returning the output of each branch by its id
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.1));
+    ///new_net.add_branch("value", vec![LayerTypes::DENSE(1, Activations::LEAKYRELU(1.0), 0.1)]);
+    ///new_net.add_branch("policy", vec![LayerTypes::DENSE(3, Activations::SOFTMAX, 0.1)]);
+    ///new_net.compile();
+    ///
+    ///let heads = new_net.predict_heads(vec![1.0, 0.0]).unwrap();
+    ///assert_eq!(heads["value"].len(), 1);
+    ///assert_eq!(heads["policy"].len(), 3);
+    ///```
+    pub fn predict_heads(&mut self, input: Vec<f32>) -> Result<HashMap<String, Vec<f32>>, NetworkError> {
+        let trunk = self.feed_forward(&input)?;
+        let mut res = HashMap::new();
+        for (id, branch) in self.branches.iter_mut() {
+            res.insert(id.clone(), branch.feed_forward(&trunk)?);
+        }
+        Ok(res)
+    }
+    ///Runs a single input through the network like predict, but returns the output layer's data
+    ///as it's kept by the layer instead of flattening it into a Vec. For a Dense output layer
+    ///that's a column with one row per output
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, input::Input};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let res = new_net.predict_matrix(vec![1.0, 0.0]).unwrap();
+    ///assert_eq!((res.rows, res.columns), (2, 1));
+    ///assert_eq!(res.to_param(), new_net.predict(vec![1.0, 0.0]).unwrap());
+    ///```
+    pub fn predict_matrix(&mut self, input: Vec<f32>) -> Result<Matrix, NetworkError> {
+        self.feed_forward(&input)?;
+        Ok(Matrix::from(self.layers[self.layers.len()-1].get_data().to_param_2d()))
+    }
+    ///Runs a single input forward up to and including the layer at `layer_index`, returning that
+    ///layer's output. Handy for looking at what hidden layers learned, or for using the first
+    ///layers of a network as a feature extractor
+    ///
+    ///Returns NetworkError::InvalidLayer if there is no compiled layer at `layer_index`
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(5, Activations::RELU, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let hidden = new_net.layer_output(vec![1.0, 0.0], 0).unwrap();
+    ///assert_eq!(hidden.len(), 5);
+    ///assert!(hidden.iter().all(|value| *value >= 0.0));
+    ///assert_eq!(new_net.layer_output(vec![1.0, 0.0], 1).unwrap(), new_net.predict(vec![1.0, 0.0]).unwrap());
+    ///assert!(new_net.layer_output(vec![1.0, 0.0], 2).is_err());
+    ///```
+    pub fn layer_output(&mut self, input: Vec<f32>, layer_index: usize) -> Result<Vec<f32>, NetworkError> {
+        if layer_index >= self.layers.len() {
+            return Err(NetworkError::InvalidLayer { index: layer_index, reason: format!("the network only has {} compiled layers", self.layers.len()) });
+        }
+        self.check_input(&input)?;
+
+        let mut data_at: Box<dyn Input> = Box::new(input);
+        for layer in self.layers[..=layer_index].iter_mut() {
+            data_at = layer.forward(&*data_at);
+        }
+        Ok(data_at.to_param())
+    }
+    ///The gradient of the loss between the network's output for `input` and `target` with
+    ///respect to every value of `input`, the direction each input value would have to move to
+    ///increase the loss (scaled the same way the errors of back propegation are). This is the
+    ///basis of saliency maps and adversarial examples
+    ///
+    ///The input is run with layers like Dropout turned off, and the network is left exactly as it
+    ///was, no weights are updated and no gradients are accumulated
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(3);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let input = vec![0.2, -0.4, 0.9];
+    ///let before = new_net.predict(input.clone()).unwrap();
+    ///let gradient = new_net.input_gradient(input.clone(), vec![1.0]).unwrap();
+    ///assert_eq!(gradient.len(), 3);
+    ///assert_eq!(new_net.predict(input.clone()).unwrap(), before);
+    ///
+    /////Stepping the input against the gradient moves the output towards the target
+    ///let stepped: Vec<f32> = input.iter().zip(gradient.iter()).map(|(x, grad)| x - 0.5 * grad).collect();
+    ///assert!(new_net.predict(stepped).unwrap()[0] > before[0]);
+    ///```
+    pub fn input_gradient(&mut self, input: Vec<f32>, target: Vec<f32>) -> Result<Vec<f32>, NetworkError> {
+        let layers = self.layers.clone();
+        let mode = self.mode;
+        self.set_mode(Mode::EVAL);
+
+        let errors = self.feed_forward(&input).and_then(|outputs| self.back_propegate(&input, outputs, &target));
+
+        //Restoring the layers throws away the gradients the pass accumulated
+        self.layers = layers;
+        self.set_mode(mode);
+        Ok(errors?.to_param().iter().map(|error| -error).collect())
+    }
+    ///How much every input feature drives the network's predictions over `inputs`, the absolute
+    ///gradient of the network's outputs (summed) with respect to each feature averaged across the
+    ///samples and normalized so the importances sum to 1. Features the outputs don't depend on at
+    ///all score 0, and so does every feature if none of them matter
+    ///
+    ///Like input_gradient the samples are run with layers like Dropout turned off and the network
+    ///is left exactly as it was
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    /////The target only depends on the first feature
+    ///let inputs: Vec<Vec<f32>> = (0..20).map(|i| vec![(i % 5) as f32 / 5.0, (i / 5) as f32 / 4.0]).collect();
+    ///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| vec![input[0] * 2.0]).collect();
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::LEAKYRELU(1.0), 0.05));
+    ///new_net.compile();
+    ///new_net.fit(&inputs, &outputs, 300).unwrap();
+    ///
+    ///let importance = new_net.feature_importance(&inputs).unwrap();
+    ///assert!((importance.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    ///assert!(importance[0] > 0.9);
+    ///```
+    pub fn feature_importance(&mut self, inputs: &[Vec<f32>]) -> Result<Vec<f32>, NetworkError> {
+        let layers = self.layers.clone();
+        let mode = self.mode;
+        self.set_mode(Mode::EVAL);
+
+        let mut res = vec![0.0; self.layer_sizes[0]];
+        let mut sensitivity = || -> Result<(), NetworkError> {
+            for input in inputs {
+                let outputs = self.feed_forward(input)?;
+                let errors = self.propegate_errors(input, vec![1.0; outputs.len()]).to_param();
+                res.iter_mut().zip(errors.iter()).for_each(|(sum, error)| *sum += error.abs());
+            }
+            Ok(())
+        };
+        let measured = sensitivity();
+
+        //Restoring the layers throws away the gradients the passes accumulated
+        self.layers = layers;
+        self.set_mode(mode);
+        measured?;
+
+        let total: f32 = res.iter().sum();
+        if total > 0.0 {
+            res.iter_mut().for_each(|importance| *importance /= total);
+        }
+        Ok(res)
+    }
+    ///Runs a single input through a classifier and decodes its output into a class label, the
+    ///index of the largest output or 0 and 1 thresholded at 0.5 for a single output
+    ///
+    ///Returns NetworkError::NotAClassifier unless the network's task is Task::CLASSIFICATION, use
+    ///predict for the raw outputs of a regression
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, modes::Task};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::SOFTMAX, 0.1));
+    ///new_net.compile();
+    ///assert!(new_net.predict_class(vec![1.0, 0.0]).is_err());
+    ///
+    ///new_net.set_task(Task::CLASSIFICATION);
+    ///assert!(new_net.predict_class(vec![1.0, 0.0]).unwrap() < 4);
+    ///```
+    pub fn predict_class(&mut self, input: Vec<f32>) -> Result<usize, NetworkError> {
+        if self.task != Task::CLASSIFICATION {
+            return Err(NetworkError::NotAClassifier);
+        }
+        Ok(class_of(&self.predict(input)?))
+    }
+    ///Runs a single input through the network and turns its output into class probabilities that
+    ///sum to 1, however the output layer is set up
+    ///
+    ///* A SOFTMAX output (including a SOFTMAXCE layer) already is a probability distribution, so
+    ///  it's returned exactly as predict would
+    ///* A single output is read as the probability of class 1 (clamped to [0, 1]), giving
+    ///  `[1 - p, p]` to match the two classes predict_class decides between
+    ///* Any other output is treated as raw scores and normalized with a softmax
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::TANH, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::RELU, 0.1));
+    ///new_net.compile();
+    ///
+    ///let probabilities = new_net.predict_proba(vec![1.0, 0.5]).unwrap();
+    ///assert_eq!(probabilities.len(), 4);
+    ///assert!((probabilities.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+    ///
+    ///let mut binary = Network::new(2);
+    ///binary.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///binary.compile();
+    ///let p = binary.predict(vec![1.0, 0.5]).unwrap()[0];
+    ///assert_eq!(binary.predict_proba(vec![1.0, 0.5]).unwrap(), vec![1.0 - p, p]);
+    ///```
+    pub fn predict_proba(&mut self, input: Vec<f32>) -> Result<Vec<f32>, NetworkError> {
+        let outputs = self.predict(input)?;
+        Ok(self.to_probabilities(outputs))
+    }
+    ///The `k` most likely classes for a single input and their probabilities (see predict_proba),
+    ///most likely first. Every class is returned when there are fewer than `k`, and ties go to the
+    ///lowest class
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, data::argmax_decode};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(6, Activations::SOFTMAX, 0.1));
+    ///new_net.compile();
+    ///
+    ///let probabilities = new_net.predict_proba(vec![0.3, -0.7]).unwrap();
+    ///let top = new_net.predict_top_k(vec![0.3, -0.7], 3).unwrap();
+    ///assert_eq!(top.len(), 3);
+    ///assert_eq!(top[0].0, argmax_decode(&[probabilities.clone()])[0]);
+    ///assert!(top.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    ///assert!(top.iter().all(|&(class, probability)| probabilities[class] == probability));
+    ///
+    ///assert_eq!(new_net.predict_top_k(vec![0.3, -0.7], 10).unwrap().len(), 6);
+    ///```
+    pub fn predict_top_k(&mut self, input: Vec<f32>, k: usize) -> Result<Vec<(usize, f32)>, NetworkError> {
+        let mut res: Vec<(usize, f32)> = self.predict_proba(input)?.into_iter().enumerate().collect();
+        let by_probability = |a: &(usize, f32), b: &(usize, f32)| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0));
+        if k < res.len() {
+            if k > 0 {
+                res.select_nth_unstable_by(k - 1, by_probability);
+            }
+            res.truncate(k);
+        }
+        res.sort_by(by_probability);
+        Ok(res)
+    }
+    fn to_probabilities(&self, outputs: Vec<f32>) -> Vec<f32> {
+        if let Some(Activations::SOFTMAX) = self.layers[self.layers.len()-1].get_activation() {
+            return outputs;
+        }
+        if outputs.len() == 1 {
+            let p = outputs[0].clamp(0.0, 1.0);
+            return vec![1.0 - p, p];
+        }
+        Activations::SOFTMAX.apply(Matrix::from(vec![outputs]).transpose()).flatten()
+    }
+    ///Runs a single input forward one layer at a time, checking every exit attached with
+    ///add_exit along the way. As soon as an exit's most likely class has a probability above
+    ///`confidence_threshold` its probabilities are returned, skipping every layer after it.
+    ///Otherwise the probabilities of the network's own output are returned (see predict_proba)
+    ///
+    ///Also returns how many of the network's layers were run, fewer than there are layers when an
+    ///exit was taken
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, modes::Task};
+    ///
+    ///let inputs: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32 / 20.0]).collect();
+    ///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| if input[0] > 0.5 { vec![0.0, 1.0] } else { vec![1.0, 0.0] }).collect();
+    ///
+    ///let mut new_net = Network::new(1);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.05));
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.05));
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SOFTMAX, 0.05));
+    ///new_net.add_exit(0, vec![LayerTypes::DENSE(2, Activations::SOFTMAX, 0.05)]).unwrap();
+    ///new_net.set_task(Task::CLASSIFICATION);
+    ///new_net.compile();
+    ///
+    ///new_net.fit(&inputs, &outputs, 200).unwrap();
+    ///new_net.fit_exits(&inputs, &outputs, 200).unwrap();
+    ///
+    /////Samples far from the boundary between the classes are easy enough for the exit, the ones
+    /////right on it go through the whole network
+    ///let (probabilities, layers_run) = new_net.predict_with_early_exit(vec![0.0], 0.8).unwrap();
+    ///assert_eq!(layers_run, 1);
+    ///assert!(probabilities[0] > 0.8);
+    ///
+    ///let (probabilities, layers_run) = new_net.predict_with_early_exit(vec![0.525], 0.8).unwrap();
+    ///assert_eq!(layers_run, 3);
+    ///assert_eq!(probabilities, new_net.predict_proba(vec![0.525]).unwrap());
+    ///
+    /////Any exit is confident enough for a threshold of 0, and none is for a threshold of 1
+    ///assert_eq!(new_net.predict_with_early_exit(vec![0.525], 0.0).unwrap().1, 1);
+    ///assert_eq!(new_net.predict_with_early_exit(vec![0.0], 1.0).unwrap().1, 3);
+    ///```
+    pub fn predict_with_early_exit(&mut self, input: Vec<f32>, confidence_threshold: f32) -> Result<(Vec<f32>, usize), NetworkError> {
+        self.check_input(&input)?;
+
+        let mut data_at: Box<dyn Input> = Box::new(input);
+        for (index, layer) in self.layers.iter_mut().enumerate() {
+            data_at = layer.forward(&*data_at);
+            for (_, exit) in self.exits.iter_mut().filter(|(after_layer, _)| *after_layer == index) {
+                let probabilities = exit.predict_proba(data_at.to_param())?;
+                if probabilities.iter().copied().fold(f32::NEG_INFINITY, f32::max) > confidence_threshold {
+                    return Ok((probabilities, index + 1));
+                }
+            }
+        }
+        Ok((self.to_probabilities(data_at.to_param()), self.layers.len()))
+    }
+    ///Runs a batch of inputs through the network at once, returning one output per input
+    ///
+    ///The inputs are stacked into a single Matrix so each layer's weights are only multiplied
+    ///once for the whole batch. Layers that work on a single 2D feature map (pooling, flatten)
+    ///can't be batched this way, predict should be used for those networks instead
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::TANH, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SOFTMAX, 0.1));
+    ///new_net.compile();
+    ///
+    ///let batched = new_net.predict_batch(&inputs).unwrap();
+    ///for (input, output) in inputs.iter().zip(batched.iter()) {
+    ///    assert_eq!(&new_net.predict(input.clone()).unwrap(), output);
+    ///}
+    ///```
+    pub fn predict_batch(&mut self, inputs: &[Vec<f32>]) -> Result<Vec<Vec<f32>>, NetworkError> {
+        if inputs.is_empty() {
+            return Ok(vec![]);
+        }
+        for input in inputs.iter() {
+            self.check_input(input)?;
+        }
+
+        let mut data_at: Box<dyn Input> = Box::new(inputs.to_vec());
+        for layer in self.layers.iter_mut(){
+            data_at = layer.forward(&*data_at);
+        }
+        Ok(data_at.to_param_2d())
+    }
+    ///Runs a batch of inputs through the network across every thread in rayon's pool, returning
+    ///one output per input in the same order as the inputs
+    ///
+    ///Layers store the data they produce when fed forward, so they can't be shared between
+    ///threads. Instead the inputs are split into one chunk per thread and every chunk is fed
+    ///through its own copy of the layers, leaving the network itself untouched. Copying the
+    ///layers has a fixed cost, so this only pays off on batches much larger than the threads
+    ///available, see `benches/predict.rs`
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let parallel = new_net.predict_parallel(&inputs).unwrap();
+    ///for (input, output) in inputs.iter().zip(parallel.iter()) {
+    ///    assert_eq!(&new_net.predict(input.clone()).unwrap(), output);
+    ///}
+    ///```
+    pub fn predict_parallel(&self, inputs: &[Vec<f32>]) -> Result<Vec<Vec<f32>>, NetworkError> {
+        if inputs.is_empty() {
+            return Ok(vec![]);
+        }
+        for input in inputs.iter() {
+            self.check_input(input)?;
+        }
+
+        let chunk_size = inputs.len().div_ceil(rayon::current_num_threads());
+
+        let chunks: Vec<Vec<Vec<f32>>> = inputs.par_chunks(chunk_size).map(|chunk| {
+            let mut layers = self.layers.clone();
+            chunk.iter().map(|input| {
+                let mut data_at: Box<dyn Input> = Box::new(input.clone());
+                for layer in layers.iter_mut() {
+                    data_at = layer.forward(&*data_at);
+                }
+                data_at.to_param()
+            }).collect()
+        }).collect();
+
+        Ok(chunks.into_iter().flatten().collect())
+    }
+    ///The fraction of samples the network classifies correctly, comparing the index of the largest
+    ///output against the index of the largest target. Networks with a single output are treated
+    ///as binary classifiers, thresholding both the output and the target at 0.5
+    ///
+    ///Samples that don't fit the network count as incorrect, and an empty set has an accuracy of 0
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let accuracy = new_net.accuracy(&inputs, &outputs);
+    ///assert!((0.0..=1.0).contains(&accuracy));
+    ///assert_eq!(new_net.accuracy(&[vec![1.0]], &[vec![1.0]]), 0.0);
+    ///```
+    pub fn accuracy(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>]) -> f32 {
+        if inputs.is_empty() {
+            return 0.0;
+        }
+        let mut correct = 0;
+        for (input, target) in inputs.iter().zip(targets.iter()) {
+            let outputs = match self.predict(input.clone()) {
+                Ok(outputs) => outputs,
+                Err(_) => continue,
+            };
+            if self.loss_fn.target_size(outputs.len()) == target.len() && class_of(&outputs) == self.target_class(target) {
+                correct += 1;
+            }
+        }
+        correct as f32 / inputs.len() as f32
+    }
+    ///Measures the network over a set of samples in a single pass, returning the mean loss
+    ///(according to the loss function the network trains with) along with the accuracy, scored the
+    ///same way as accuracy. Layers like Dropout are turned off and no weights are updated
+    ///
+    ///Returns a NetworkError if any of the samples do not fit the network, an empty set scores 0
+    ///on both
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new_seeded(2, 1);
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::LEAKYRELU(1.0), 0.1));
+    ///new_net.compile();
+    /////Outputs x1 + x2, so the XOR samples come out as 0, 1, 1 and 2
+    ///new_net.layers[0].set_parameters(&[1.0, 1.0, 0.0]);
+    ///
+    ///let result = new_net.evaluate(&inputs, &outputs).unwrap();
+    /////Only the last sample is off, by 2 (a squared error of 4) and on the wrong side of 0.5
+    ///assert_eq!(result.loss, 1.0);
+    ///assert_eq!(result.accuracy, 0.75);
+    ///assert_eq!(result.accuracy, new_net.accuracy(&inputs, &outputs));
+    ///assert!(new_net.evaluate(&[vec![1.0]], &[vec![1.0]]).is_err());
+    ///```
+    pub fn evaluate(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>]) -> Result<EvalResult, NetworkError> {
+        let mode = self.mode;
+        self.set_mode(Mode::EVAL);
+        let result = self.evaluate_samples(inputs, targets);
+        self.set_mode(mode);
+        result
+    }
+    fn evaluate_samples(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>]) -> Result<EvalResult, NetworkError> {
+        let mut result = EvalResult::default();
+        for (input, target) in inputs.iter().zip(targets.iter()) {
+            let outputs = self.feed_forward(input)?;
+            if self.loss_fn.target_size(outputs.len()) != target.len() {
+                return Err(NetworkError::OutputSizeMismatch { expected: self.loss_fn.target_size(outputs.len()), got: target.len() });
+            }
+            result.loss += self.sample_loss(&outputs, target);
+            if class_of(&outputs) == self.target_class(target) {
+                result.accuracy += 1.0;
+            }
+        }
+
+        if !inputs.is_empty() {
+            result.loss /= inputs.len() as f32;
+            result.accuracy /= inputs.len() as f32;
+        }
+        Ok(result)
+    }
+    ///Sweeps the thresholds 0, 0.01, ..., 1 a binary classifier's single output could be cut off
+    ///at, returning the one with the best F1 score over the samples (the lowest one on ties).
+    ///When positives are rare the default cut off of 0.5 can miss most of them
+    ///
+    ///Samples that don't fit the network are left out, and 0.5 is returned if none are left
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(1);
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    /////The output is sigmoid(x), so positives score 0.3 and negatives 0.05
+    ///new_net.layers[0].set_parameters(&[1.0, 0.0]);
+    ///let inputs = vec![vec![(0.3f32 / 0.7).ln()], vec![(0.05f32 / 0.95).ln()], vec![(0.05f32 / 0.95).ln()]];
+    ///let targets = vec![vec![1.0], vec![0.0], vec![0.0]];
+    ///
+    ///let threshold = new_net.best_threshold(&inputs, &targets);
+    ///assert!(threshold > 0.05 && threshold <= 0.3);
+    ///```
+    pub fn best_threshold(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>]) -> f32 {
+        let mut scores: Vec<f32> = vec![];
+        let mut labels: Vec<f32> = vec![];
+        for (input, target) in inputs.iter().zip(targets.iter()) {
+            if let Ok(outputs) = self.predict(input.clone()) {
+                if let (Some(score), Some(label)) = (outputs.first(), target.first()) {
+                    scores.push(*score);
+                    labels.push(*label);
+                }
+            }
+        }
+        if scores.is_empty() {
+            return 0.5;
+        }
+
+        let mut best = (0.5, f32::NEG_INFINITY);
+        for step in 0..=100 {
+            let threshold = step as f32 / 100.0;
+            let f1 = metrics::f1_score(&scores, &labels, threshold);
+            if f1 > best.1 {
+                best = (threshold, f1);
+            }
+        }
+        best.0
+    }
+    ///Counts how every sample is classified, indexed by `[actual][predicted]` class. Classes are
+    ///the index of the largest value, or 0 and 1 thresholded at 0.5 for a single output
+    ///
+    ///Samples that don't fit the network, or whose classes are `num_classes` or higher, are left
+    ///out of the counts
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///use triton_grow::helper::data_vis::pretty_print_confusion;
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SOFTMAX, 0.1));
+    ///new_net.compile();
+    ///
+    ///let confusion = new_net.confusion_matrix(&inputs, &outputs, 3);
+    ///assert_eq!(confusion.iter().flatten().sum::<usize>(), 3);
+    ///assert!(confusion.iter().all(|row| row.iter().sum::<usize>() == 1));
+    ///println!("{}", pretty_print_confusion(&confusion));
+    ///```
+    pub fn confusion_matrix(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>], num_classes: usize) -> Vec<Vec<usize>> {
+        let mut res = vec![vec![0; num_classes]; num_classes];
+        for (input, target) in inputs.iter().zip(targets.iter()) {
+            let outputs = match self.predict(input.clone()) {
+                Ok(outputs) => outputs,
+                Err(_) => continue,
+            };
+            let (actual, predicted) = (self.target_class(target), class_of(&outputs));
+            if actual < num_classes && predicted < num_classes {
+                res[actual][predicted] += 1;
+            }
+        }
+        res
+    }
+    ///Travels through a neural network's abstracted Layers and returns the resultant vector at the
+    ///end
+    ///
+    ///# Arguments
+    ///* `input_obj` - Any structure that implements the Input trait to act as an input to the data
+    ///# Returns
+    ///A vector at the end of the feed forward, or a NetworkError::InputSizeMismatch if the input
+    ///does not fit the input layer
+    ///
+    ///# Examples
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SIGMOID, 0.01));
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.01));
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::SIGMOID, 0.01));
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::TANH, 0.01));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.01));
+    ///
+    ///new_net.compile();
+    ///
+    ///let res = new_net.predict(vec![1.0, 0.54]);
+    ///assert!(res.is_ok());
+    ///assert!(new_net.predict(vec![1.0]).is_err());
+    ///```
+    fn feed_forward(&mut self, input_obj: &dyn Input) -> Result<Vec<f32>, NetworkError> {
+        self.check_input(input_obj)?;
+
+        let mut data_at: Box<dyn Input> = Box::new(input_obj.to_param());
+        for layer in self.layers.iter_mut(){
+            data_at = layer.forward(&*data_at);
+        }
+        Ok(data_at.to_param())
+    }
+    ///Makes sure an input can be fed to the first layer, both its width and its values
+    fn check_input(&self, input_obj: &dyn Input) -> Result<(), NetworkError> {
+        if let Some(expected) = self.layers[0].input_size() {
+            if input_obj.shape().0 != expected {
+                return Err(NetworkError::InputSizeMismatch { expected, got: input_obj.shape().0 });
+            }
+        }
+        self.layers[0].check_input(input_obj)
+    }
+    ///Travels backwards through a neural network and accumulates the gradients of each layer's
+    ///weights and biases, these are only applied to the layers once update_layers is called
+    ///
+    ///The backward behavior is different depending on the layer type, and therefore the weight and
+    ///bias updating is different as well
+    ///
+    ///When constructing a neural network, be cautious that your layers behave well with each other
+    ///
+    ///Returns the errors that made it all the way back to the input
+    fn back_propegate(&mut self, input_obj: &dyn Input, outputs: Vec<f32>, target_obj: &dyn Input) -> Result<Box<dyn Input>, NetworkError> {
+        let expected = self.loss_fn.target_size(outputs.len());
+        if target_obj.shape().0 != expected {
+            return Err(NetworkError::OutputSizeMismatch { expected, got: target_obj.shape().0 });
+        }
+        if self.loss_fn == Loss::SPARSECROSSENTROPY {
+            let label = target_obj.to_param()[0];
+            if label < 0.0 || label.fract() != 0.0 || label as usize >= outputs.len() {
+                return Err(NetworkError::InvalidClassLabel { label, classes: outputs.len() });
+            }
+        }
+        let activation = self.layers[self.layers.len()-1].get_activation();
+        if activation.is_none() {
+            return Err(NetworkError::InvalidOutputLayer);
+        }
+        
+        let targets = target_obj.to_param();
+        let output_errors = match self.loss_fn {
+            Loss::CROSSENTROPY if self.label_smoothing > 0.0 => Loss::CROSSENTROPY.error(&outputs, &smooth_labels(&targets, self.label_smoothing), activation),
+            Loss::SPARSECROSSENTROPY if self.label_smoothing > 0.0 => {
+                let mut target = vec![0.0; outputs.len()];
+                if let Some(value) = target.get_mut(sparse_class(&targets)) {
+                    *value = 1.0;
+                }
+                Loss::CROSSENTROPY.error(&outputs, &smooth_labels(&target, self.label_smoothing), activation)
+            },
+            _ => self.loss_fn.error(&outputs, &targets, activation),
+        };
+        Ok(self.propegate_errors(input_obj, output_errors))
+    }
+    ///Back propegates `output_errors` (one per output of the last layer) through every layer,
+    ///returning the errors of the input
+    fn propegate_errors(&mut self, input_obj: &dyn Input, output_errors: Vec<f32>) -> Box<dyn Input> {
+        let mut gradients: Box<dyn Input>;
+        let mut errors: Box<dyn Input> = Box::new(Matrix::from(output_errors.to_param_2d()).transpose());
+
+        for i in (0..self.layers.len()).rev() {
+            gradients = self.layers[i].update_gradient();
+            let data_box: Box<dyn Input> = if i == 0 {
+                Box::new(Matrix::from(input_obj.to_param_2d()).transpose())
+            } else {
+                self.layers[i - 1].get_data()
+            };
+            errors = self.layers[i].backward(gradients, errors, data_box);
+        }
+        errors
+    }
+    ///Applies the gradients every layer has accumulated during back propegation, scaled by `scale`
+    fn update_layers(&mut self, scale: f32) {
+        if self.track_layer_stats {
+            self.layer_stats = self.layers.iter().map(|layer| {
+                let output = layer.get_data().to_param();
+                LayerStats {
+                    weight_norm: l2_norm(&layer.parameters()),
+                    grad_norm: l2_norm(&layer.parameter_gradients()) * scale.abs(),
+                    mean_activation: output.iter().sum::<f32>() / output.len().max(1) as f32,
+                }
+            }).collect();
+        }
+        for layer in self.layers.iter_mut() {
+            layer.apply_gradients(scale);
+        }
+    }
+    ///The loss between a network output and its target according to the network's loss function
+    fn sample_loss(&self, outputs: &[f32], targets: &[f32]) -> f32 {
+        self.loss_fn.compute(outputs, targets, self.layers[self.layers.len()-1].get_activation())
+    }
+    ///The class a target stands for, its class index under Loss::SPARSECROSSENTROPY and decoded
+    ///like an output otherwise
+    fn target_class(&self, targets: &[f32]) -> usize {
+        match self.loss_fn {
+            Loss::SPARSECROSSENTROPY => sparse_class(targets),
+            _ => class_of(targets),
+        }
+    }
+    ///The RNG the training data is shuffled with, seeded from the network's seed so a seeded
+    ///network always visits the samples in the same order
+    fn shuffle_rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed.unwrap_or_else(|| thread_rng().gen()))
+    }
+    ///Adds the accuracy of an epoch with `correct` of `samples` classified correctly to `history`,
+    ///classifiers only
+    fn record_accuracy(&self, history: &mut History, correct: usize, samples: usize) {
+        if self.task == Task::CLASSIFICATION {
+            history.accuracy.push(correct as f32 / samples.max(1) as f32);
+        }
+    }
+    ///Trains a neural network by iteratively feeding forward a series of inputs and then doing
+    ///back propegation based on the outputs supplied
+    ///
+    ///# Arguments
+    ///* `train_in` - A slice of objects that implement the Input trait, used as the training
+    ///  input
+    ///* `train_out` - A slice of objects that implement the Input trait, used as the results
+    ///  compared to what is actually derived during back propegation
+    ///* `epochs` - How many epochs you want your model training for
+    ///
+    ///The order of the training data is shuffled at the start of every epoch, use fit_seeded for
+    ///a reproducible order. The training data is only borrowed, so it can be reused afterwards
+    ///
+    ///Returns the History of the training, or a NetworkError if any of the samples do not fit the
+    ///network's input or output layer. Returns NetworkError::SampleCountMismatch if `train_in` and
+    ///`train_out` hold a different number of samples
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, error::NetworkError};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let history = new_net.fit(&inputs, &outputs, 3).unwrap();
+    ///assert_eq!(history.loss, new_net.get_loss_history());
+    ///assert!(history.val_loss.is_none() && history.accuracy.is_empty());
+    ///new_net.fit(&inputs[..2], &outputs[..2], 1).unwrap();
+    ///
+    ///assert!(new_net.fit(&inputs, &outputs, 0).unwrap().loss.is_empty());
+    ///assert!(new_net.get_loss_history().is_empty());
+    ///
+    ///let mismatch = Err(NetworkError::SampleCountMismatch { inputs: 4, targets: 3 });
+    ///assert_eq!(new_net.fit(&inputs, &outputs[..3], 1), mismatch);
+    ///assert_eq!(new_net.fit_batched(&inputs, &outputs[..3], 1, 2), mismatch);
+    ///assert_eq!(new_net.fit_with_validation(&inputs, &outputs[..3], &inputs, &outputs, 1, 1), mismatch);
+    ///assert_eq!(new_net.cross_validate(&inputs, &outputs[..3], 2, 1), Err(NetworkError::SampleCountMismatch { inputs: 4, targets: 3 }));
+    ///```
+    ///
+    ///Classifiers also track how many samples they got right
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, modes::Task};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![1.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.set_task(Task::CLASSIFICATION);
+    ///new_net.compile();
+    ///
+    ///let history = new_net.fit(&inputs, &outputs, 200).unwrap();
+    ///assert_eq!(history.accuracy.len(), 200);
+    ///assert_eq!(history.accuracy[199], 1.0);
+    ///```
+    pub fn fit<Param: Input>(&mut self, train_in: &[Param], train_out: &[Param], epochs: usize) -> Result<History, NetworkError> {
+        let seed: u64 = thread_rng().gen();
+        self.fit_seeded(train_in, train_out, epochs, seed)
+    }
+    ///Trains a neural network exactly like fit, but shuffles the training data with an RNG seeded
+    ///by `seed` so the same seed always visits the samples in the same order
+    ///
+    ///Each input stays paired with its own output through the shuffle
+    pub fn fit_seeded<Param: Input>(&mut self, train_in: &[Param], train_out: &[Param], epochs: usize, seed: u64) -> Result<History, NetworkError> {
+        self.fit_shuffled(train_in, train_out, epochs, seed, &mut |_, _| {})
+    }
+    ///Trains a neural network exactly like fit, calling `callback` once at the end of every epoch
+    ///with the epoch index and the mean loss across that epoch's samples
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let mut curve: Vec<(usize, f32)> = vec![];
+    ///new_net.fit_with_callback(&inputs, &outputs, 2, |epoch, loss| curve.push((epoch, loss))).unwrap();
+    ///assert_eq!(curve.len(), 2);
+    ///```
+    pub fn fit_with_callback<Param: Input, F: FnMut(usize, f32)>(&mut self, train_in: &[Param], train_out: &[Param], epochs: usize, mut callback: F) -> Result<History, NetworkError> {
+        let seed: u64 = thread_rng().gen();
+        self.fit_shuffled(train_in, train_out, epochs, seed, &mut callback)
+    }
+    fn fit_shuffled<Param: Input>(&mut self, train_in: &[Param], train_out: &[Param], epochs: usize, seed: u64, callback: &mut dyn FnMut(usize, f32)) -> Result<History, NetworkError> {
+        check_sample_count(train_in.len(), train_out.len())?;
+        self.loss_train = vec![];
+        let mut history = History::default();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut order: Vec<usize> = (0..train_in.len()).collect();
+
+        let iterations_per_epoch = self.iterations_per_epoch.unwrap_or(1);
+
+        for epoch in 0..epochs {
+            let start = Instant::now();
+            self.schedule_learning_rates(epoch);
+            let mut loss: f32 = 0.0;
+            let mut correct = 0;
+            order.shuffle(&mut rng);
+            for _ in 0..iterations_per_epoch {
+                for &input_index in order.iter() {
+                    let outputs = self.feed_forward(&train_in[input_index])?;
+                    self.back_propegate(&train_in[input_index], outputs.clone(), &train_out[input_index])?;
+                    self.update_layers(1.0);
+
+                    let targets = train_out[input_index].to_param();
+                    loss += self.sample_loss(&outputs, &targets);
+                    correct += (class_of(&outputs) == self.target_class(&targets)) as usize;
+                }
+            }
+            let epoch_loss = loss / (iterations_per_epoch * train_in.len()) as f32;
+            self.loss_train.push(epoch_loss);
+            self.record_accuracy(&mut history, correct, iterations_per_epoch * train_in.len());
+            self.log_epoch(epoch, epoch_loss, start.elapsed());
+            self.check_divergence(epoch, epoch_loss)?;
+            callback(epoch, epoch_loss);
+        }
+
+        if let Some(loss) = self.loss_train.last() {
+            self.loss = *loss;
+        }
+        self.log_trained();
+        history.loss = self.loss_train.clone();
+        Ok(history)
+    }
+    ///Trains the trunk and every branch added with add_branch together, each sample of
+    ///`train_out` holding the target of every branch by its id. Every branch's error is measured
+    ///with its own loss function and the errors all branches send back are summed before they go
+    ///through the trunk, so the shared layers learn from every task at once
+    ///
+    ///The loss of the returned History (and the loss history of the network) is the summed loss
+    ///of all branches, while every branch keeps its own in its get_loss_history
+    ///
+    ///Returns NetworkError::MissingHeadTarget if a sample has no target for one of the branches
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use std::collections::HashMap;
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, loss::Loss, modes::Task};
+    ///
+    /////A regression head learning the mean of the inputs, and a classification head learning
+    /////whether the first input is the largest
+    ///let inputs: Vec<Vec<f32>> = (0..16).map(|i| vec![(i % 4) as f32 / 4.0, (i / 4) as f32 / 4.0]).collect();
+    ///let targets: Vec<HashMap<String, Vec<f32>>> = inputs.iter().map(|input| HashMap::from([
+    ///    ("mean".to_string(), vec![(input[0] + input[1]) / 2.0]),
+    ///    ("largest".to_string(), vec![(input[0] > input[1]) as u8 as f32]),
+    ///])).collect();
+    ///
+    ///let mut new_net = Network::new_seeded(2, 4);
+    ///new_net.add_layer(LayerTypes::DENSE(8, Activations::TANH, 0.01));
+    ///new_net.add_branch("mean", vec![LayerTypes::DENSE(1, Activations::LEAKYRELU(1.0), 0.01)]);
+    ///new_net.add_branch("largest", vec![LayerTypes::DENSE(1, Activations::SIGMOID, 0.01)]);
+    ///new_net.compile();
+    ///new_net.branch_mut("largest").unwrap().set_loss(Loss::CROSSENTROPY);
+    ///new_net.branch_mut("largest").unwrap().set_task(Task::CLASSIFICATION);
+    ///
+    ///let history = new_net.fit_heads(&inputs, &targets, 300).unwrap();
+    ///assert_eq!(history.loss, new_net.get_loss_history());
+    ///
+    ///let mean_loss = new_net.branch_mut("mean").unwrap().get_loss_history();
+    ///let largest_loss = new_net.branch_mut("largest").unwrap().get_loss_history();
+    ///assert!(mean_loss[299] < mean_loss[0] / 10.0);
+    ///assert!(largest_loss[299] < largest_loss[0] / 2.0);
+    ///
+    ///for (input, target) in inputs.iter().zip(targets.iter()) {
+    ///    let heads = new_net.predict_heads(input.clone()).unwrap();
+    ///    assert!((heads["mean"][0] - target["mean"][0]).abs() < 0.2);
+    ///    assert_eq!(heads["largest"][0] > 0.5, target["largest"][0] > 0.5);
+    ///}
+    ///```
+    pub fn fit_heads(&mut self, train_in: &[Vec<f32>], train_out: &[HashMap<String, Vec<f32>>], epochs: usize) -> Result<History, NetworkError> {
+        check_sample_count(train_in.len(), train_out.len())?;
+        self.loss_train = vec![];
+        for (_, branch) in self.branches.iter_mut() {
+            branch.loss_train = vec![];
+        }
+        let mut rng = self.shuffle_rng();
+        let mut order: Vec<usize> = (0..train_in.len()).collect();
+
+        for epoch in 0..epochs {
+            let start = Instant::now();
+            self.schedule_learning_rates(epoch);
+            for (_, branch) in self.branches.iter_mut() {
+                branch.schedule_learning_rates(epoch);
+            }
+            let mut branch_losses = vec![0.0; self.branches.len()];
+            order.shuffle(&mut rng);
+            for &input_index in order.iter() {
+                let trunk_out = self.feed_forward(&train_in[input_index])?;
+                let mut trunk_errors = vec![0.0; trunk_out.len()];
+
+                for ((id, branch), branch_loss) in self.branches.iter_mut().zip(branch_losses.iter_mut()) {
+                    let target = train_out[input_index].get(id).ok_or_else(|| NetworkError::MissingHeadTarget(id.clone()))?;
+                    let outputs = branch.feed_forward(&trunk_out)?;
+                    let errors = branch.back_propegate(&trunk_out, outputs.clone(), target)?;
+                    branch.update_layers(1.0);
+
+                    trunk_errors.iter_mut().zip(errors.to_param().iter()).for_each(|(sum, error)| *sum += error);
+                    *branch_loss += branch.sample_loss(&outputs, target);
+                }
+
+                self.propegate_errors(&train_in[input_index], trunk_errors);
+                self.update_layers(1.0);
+            }
+
+            for ((_, branch), branch_loss) in self.branches.iter_mut().zip(branch_losses.iter()) {
+                branch.loss_train.push(branch_loss / train_in.len() as f32);
+            }
+            let epoch_loss = branch_losses.iter().sum::<f32>() / train_in.len() as f32;
+            self.loss_train.push(epoch_loss);
+            self.log_epoch(epoch, epoch_loss, start.elapsed());
+            self.check_divergence(epoch, epoch_loss)?;
+        }
+
+        if let Some(loss) = self.loss_train.last() {
+            self.loss = *loss;
+        }
+        self.log_trained();
+        Ok(History { loss: self.loss_train.clone(), ..History::default() })
+    }
+    ///Trains every exit attached with add_exit for `epochs` epochs to predict `train_out` from
+    ///the output of the layer it's attached to. The network's own layers are only run forward and
+    ///left as they are, so the network is usually trained first
+    ///
+    ///The loss of the returned History is the summed loss of every exit, while every exit keeps
+    ///its own in its get_loss_history. The network's own loss history is left alone
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, modes::Task, error::NetworkError};
+    ///
+    ///let inputs: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32 / 20.0]).collect();
+    ///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| if input[0] > 0.5 { vec![0.0, 1.0] } else { vec![1.0, 0.0] }).collect();
+    ///
+    ///let mut new_net = Network::new(1);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::TANH, 0.05));
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SOFTMAX, 0.05));
+    ///new_net.add_exit(0, vec![LayerTypes::DENSE(2, Activations::SOFTMAX, 0.05)]).unwrap();
+    ///new_net.set_task(Task::CLASSIFICATION);
+    ///new_net.compile();
+    ///new_net.fit(&inputs, &outputs, 50).unwrap();
+    ///let trunk_loss = new_net.get_loss_history();
+    ///
+    ///let history = new_net.fit_exits(&inputs, &outputs, 100).unwrap();
+    ///assert_eq!(history.loss.len(), 100);
+    ///assert!(history.loss[99] < history.loss[0]);
+    ///assert_eq!(new_net.get_loss_history(), trunk_loss);
+    ///
+    ///assert_eq!(new_net.fit_exits(&[vec![0.0, 1.0]], &[vec![1.0, 0.0]], 1), Err(NetworkError::InputSizeMismatch { expected: 1, got: 2 }));
+    ///```
+    pub fn fit_exits(&mut self, train_in: &[Vec<f32>], train_out: &[Vec<f32>], epochs: usize) -> Result<History, NetworkError> {
+        check_sample_count(train_in.len(), train_out.len())?;
+        for input in train_in.iter() {
+            self.check_input(input)?;
+        }
+        for (_, exit) in self.exits.iter_mut() {
+            exit.loss_train = vec![];
+        }
+        let mut rng = self.shuffle_rng();
+        let mut order: Vec<usize> = (0..train_in.len()).collect();
+        let mut history = History::default();
+
+        for epoch in 0..epochs {
+            for (_, exit) in self.exits.iter_mut() {
+                exit.schedule_learning_rates(epoch);
+            }
+            let mut exit_losses = vec![0.0; self.exits.len()];
+            order.shuffle(&mut rng);
+            for &input_index in order.iter() {
+                let mut data_at: Box<dyn Input> = Box::new(train_in[input_index].clone());
+                for (index, layer) in self.layers.iter_mut().enumerate() {
+                    data_at = layer.forward(&*data_at);
+                    for ((after_layer, exit), exit_loss) in self.exits.iter_mut().zip(exit_losses.iter_mut()) {
+                        if *after_layer != index {
+                            continue;
+                        }
+                        let hidden = data_at.to_param();
+                        let outputs = exit.feed_forward(&hidden)?;
+                        exit.back_propegate(&hidden, outputs.clone(), &train_out[input_index])?;
+                        exit.update_layers(1.0);
+                        *exit_loss += exit.sample_loss(&outputs, &train_out[input_index]);
+                    }
+                }
+            }
+
+            for ((_, exit), exit_loss) in self.exits.iter_mut().zip(exit_losses.iter()) {
+                exit.loss_train.push(exit_loss / train_in.len() as f32);
+            }
+            history.loss.push(exit_losses.iter().sum::<f32>() / train_in.len() as f32);
+        }
+        Ok(history)
+    }
+    ///Trains a neural network using mini-batch gradient descent, where one epoch is a single pass
+    ///over the training data
+    ///
+    ///The data is split into batches of `batch_size` samples, the gradients of every sample in a
+    ///batch are accumulated and the averaged update is applied once per batch. The last batch may
+    ///be smaller if the data doesn't divide evenly, and a `batch_size` of 0 is treated as 1
+    ///
+    ///# Arguments
+    ///* `train_in` - The training inputs
+    ///* `train_out` - The expected outputs for each of the training inputs
+    ///* `epochs` - How many passes over the training data to make
+    ///* `batch_size` - How many samples to average gradients over before each update
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///new_net.fit_batched(&inputs, &outputs, 10, 2).unwrap();
+    ///assert_eq!(new_net.get_loss_history().len(), 10);
+    ///```
+    pub fn fit_batched(&mut self, train_in: &[Vec<f32>], train_out: &[Vec<f32>], epochs: usize, batch_size: usize) -> Result<History, NetworkError> {
+        check_sample_count(train_in.len(), train_out.len())?;
+        self.loss_train = vec![];
+        let mut history = History::default();
+        let batch_size = batch_size.max(1);
+
+        for epoch in 0..epochs {
+            let start = Instant::now();
+            self.schedule_learning_rates(epoch);
+            let mut loss: f32 = 0.0;
+            let mut correct = 0;
+            for (batch_in, batch_out) in train_in.chunks(batch_size).zip(train_out.chunks(batch_size)) {
+                for (input, target) in batch_in.iter().zip(batch_out.iter()) {
+                    let outputs = self.feed_forward(input)?;
+                    self.back_propegate(input, outputs.clone(), target)?;
+
+                    loss += self.sample_loss(&outputs, target);
+                    correct += (class_of(&outputs) == self.target_class(target)) as usize;
+                }
+                self.update_layers(1.0 / batch_in.len() as f32);
+            }
+            self.loss_train.push(loss / train_in.len() as f32);
+            self.record_accuracy(&mut history, correct, train_in.len());
+            self.log_epoch(epoch, loss / train_in.len() as f32, start.elapsed());
+            self.check_divergence(epoch, loss / train_in.len() as f32)?;
+        }
+
+        if let Some(loss) = self.loss_train.last() {
+            self.loss = *loss;
+        }
+        self.log_trained();
+        history.loss = self.loss_train.clone();
+        Ok(history)
+    }
+    ///Runs a single sample forward and back through the network, adding its gradients to the ones
+    ///every layer has accumulated without updating any weights. Returns the sample's loss
+    ///
+    ///Call apply_accumulated once enough samples (or micro-batches) have been accumulated, which
+    ///simulates a batch too large to feed through at once
+    ///
+    ///# Example
+    ///
+    ///Accumulating 4 samples and averaging them is the same update as one batch of those 4
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///let mut batched = new_net.clone();
+    ///
+    ///for (input, output) in inputs.iter().zip(outputs.iter()) {
+    ///    new_net.accumulate_gradients(input, output).unwrap();
+    ///}
+    ///new_net.apply_accumulated(1.0 / 4.0);
+    ///batched.fit_batched(&inputs, &outputs, 1, 4).unwrap();
+    ///
+    ///for (layer, batched_layer) in new_net.layers.iter().zip(batched.layers.iter()) {
+    ///    assert_eq!(layer.parameters(), batched_layer.parameters());
+    ///}
+    ///```
+    pub fn accumulate_gradients<Param: Input>(&mut self, input: &Param, target: &Param) -> Result<f32, NetworkError> {
+        let outputs = self.feed_forward(input)?;
+        self.back_propegate(input, outputs.clone(), target)?;
+        Ok(self.sample_loss(&outputs, &target.to_param()))
+    }
+    ///Updates every layer from the gradients accumulated since the last update, scaled by
+    ///`scale`. 1 over the number of samples accumulated averages them
+    pub fn apply_accumulated(&mut self, scale: f32) {
+        self.update_layers(scale);
+    }
+    ///Trains a neural network one shuffled pass over the training data per epoch, measuring the
+    ///loss on a separate validation set after every epoch. Training stops early once the
+    ///validation loss hasn't improved for `patience` epochs in a row, and the layers are restored
+    ///to the weights they had at the best validation loss
+    ///
+    ///Returns the History of the training, whose val_loss is always set. It has an entry for every
+    ///epoch trained, so its length is the epoch training stopped at, which is `max_epochs` if the
+    ///validation loss never stalled for long enough
+    ///
+    ///The training data is shuffled every epoch, in the same order every time for a network
+    ///created with new_seeded
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let history = new_net.fit_with_validation(&inputs, &outputs, &inputs, &outputs, 50, 5).unwrap();
+    ///let stopped = history.loss.len();
+    ///assert!(stopped <= 50);
+    ///assert_eq!(new_net.get_loss_history().len(), stopped);
+    ///assert_eq!(history.val_loss.unwrap().len(), stopped);
+    ///
+    ///let mut seeded = Network::new_seeded(2, 7);
+    ///seeded.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///seeded.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///seeded.compile();
+    ///let mut copy = seeded.clone();
+    ///assert_eq!(seeded.fit_with_validation(&inputs, &outputs, &inputs, &outputs, 20, 5), copy.fit_with_validation(&inputs, &outputs, &inputs, &outputs, 20, 5));
+    ///```
+    pub fn fit_with_validation(&mut self, train_in: &[Vec<f32>], train_out: &[Vec<f32>], val_in: &[Vec<f32>], val_out: &[Vec<f32>], max_epochs: usize, patience: usize) -> Result<History, NetworkError> {
+        check_sample_count(train_in.len(), train_out.len())?;
+        check_sample_count(val_in.len(), val_out.len())?;
+        self.loss_train = vec![];
+        let mut history = History::default();
+        let mut val_losses = vec![];
+        let mut rng = self.shuffle_rng();
+        let mut order: Vec<usize> = (0..train_in.len()).collect();
+
+        let mut best_loss = f32::INFINITY;
+        let mut best_layers = self.layers.clone();
+        let mut stale_epochs = 0;
+        let mut stopped_at = max_epochs;
+
+        for epoch in 0..max_epochs {
+            let start = Instant::now();
+            self.schedule_learning_rates(epoch);
+            let mut loss: f32 = 0.0;
+            let mut correct = 0;
+            order.shuffle(&mut rng);
+            for &input_index in order.iter() {
+                let outputs = self.feed_forward(&train_in[input_index])?;
+                self.back_propegate(&train_in[input_index], outputs.clone(), &train_out[input_index])?;
+                self.update_layers(1.0);
+
+                loss += self.sample_loss(&outputs, &train_out[input_index]);
+                correct += (class_of(&outputs) == self.target_class(&train_out[input_index])) as usize;
+            }
+            self.loss_train.push(loss / train_in.len() as f32);
+            self.record_accuracy(&mut history, correct, train_in.len());
+
+            self.check_divergence(epoch, loss / train_in.len() as f32)?;
+            let val_loss = self.validation_loss(val_in, val_out)?;
+            val_losses.push(val_loss);
+            if self.verbose {
+                println!("Epoch {}: loss {:.4}, validation loss {:.4} ({:.2?})", epoch + 1, loss / train_in.len() as f32, val_loss, start.elapsed());
+            }
+            if val_loss < best_loss {
+                best_loss = val_loss;
+                best_layers = self.layers.clone();
+                stale_epochs = 0;
+            } else {
+                stale_epochs += 1;
+                if stale_epochs >= patience {
+                    stopped_at = epoch + 1;
+                    break;
+                }
+            }
+        }
+
+        //Optimizer state is restored along with the weights, so training can pick back up from here
+        self.layers = best_layers;
+        self.set_mode(self.mode);
+        if let Some(loss) = self.loss_train.last() {
+            self.loss = *loss;
+        }
+        if self.verbose {
+            println!("Stopped at epoch {} with a validation loss of {:.2}%", stopped_at, best_loss * 100.0);
+        }
+        history.loss = self.loss_train.clone();
+        history.val_loss = Some(val_losses);
+        Ok(history)
+    }
+    ///The mean loss of the network over a set of samples, evaluated with layers like Dropout
+    ///turned off
+    fn validation_loss(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>]) -> Result<f32, NetworkError> {
+        let mode = self.mode;
+        self.set_mode(Mode::EVAL);
+
+        let mut loss: f32 = 0.0;
+        for (input, target) in inputs.iter().zip(targets.iter()) {
+            let outputs = self.feed_forward(input);
+            match outputs {
+                Ok(outputs) => loss += self.sample_loss(&outputs, target),
+                Err(err) => {
+                    self.set_mode(mode);
+                    return Err(err);
+                }
+            }
+        }
+
+        self.set_mode(mode);
+        Ok(loss / inputs.len().max(1) as f32)
+    }
+
+    ///Estimates how well the network generalizes using k-fold cross-validation, returning the
+    ///mean loss on the held-out fold of every round
+    ///
+    ///The samples are split into `k` contiguous folds. Every round the weights are reset, the
+    ///network is trained for `epochs` passes over every other fold (one sample at a time) and then
+    ///measured on the fold that was held out. The network is left trained on the final round.
+    ///`k` is capped at the number of samples, and fewer than 2 folds returns no scores
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = (0..12).map(|i| vec![i as f32 / 12.0]).collect();
+    ///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| vec![input[0] * 0.5]).collect();
+    ///
+    ///let mut new_net = Network::new(1);
+    ///new_net.add_layer(LayerTypes::DENSE(4, Activations::SIGMOID, 0.05));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.05));
+    ///new_net.compile();
+    ///
+    ///let scores = new_net.cross_validate(&inputs, &outputs, 3, 5).unwrap();
+    ///assert_eq!(scores.len(), 3);
+    ///assert!(scores.iter().all(|score| score.is_finite()));
+    ///```
+    pub fn cross_validate(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>], k: usize, epochs: usize) -> Result<Vec<f32>, NetworkError> {
+        check_sample_count(inputs.len(), targets.len())?;
+        let folds = k.min(inputs.len());
+        if folds < 2 {
+            return Ok(vec![]);
+        }
+
+        let mut scores = vec![];
+        for fold in 0..folds {
+            let start = fold * inputs.len() / folds;
+            let end = (fold + 1) * inputs.len() / folds;
+
+            let train_in: Vec<Vec<f32>> = inputs[..start].iter().chain(inputs[end..].iter()).cloned().collect();
+            let train_out: Vec<Vec<f32>> = targets[..start].iter().chain(targets[end..].iter()).cloned().collect();
+
+            self.reset_weights();
+            self.fit_batched(&train_in, &train_out, epochs, 1)?;
+            scores.push(self.validation_loss(&inputs[start..end], &targets[start..end])?);
+        }
+        Ok(scores)
+    }
+
+    ///Saves a network to a JSON file at `path`, including every compiled layer's weights,
+    ///biases and optimizer state
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(2, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///new_net.fit_batched(&inputs, &outputs, 20, 1).unwrap();
+    ///
+    ///let path = std::env::temp_dir().join("triton_save_example.json");
+    ///new_net.save(path.to_str().unwrap()).unwrap();
+    ///let mut loaded = Network::load(path.to_str().unwrap()).unwrap();
+    ///
+    ///for input in inputs {
+    ///    assert_eq!(new_net.predict(input.clone()).unwrap(), loaded.predict(input).unwrap());
+    ///}
+    ///```
+    pub fn save(&self, path: &str) -> Result<(), NetworkError> {
+        let mut file = File::create(path)?;
+        let file_ser = to_string(self)?;
+        file.write_all(file_ser.as_bytes())?;
+        Ok(())
+    }
+    ///Loads a network previously written with save
+    pub fn load(path: &str) -> Result<Network, NetworkError>{
+        let mut buffer = String::new();
+        let mut file = File::open(path)?;
+
+        file.read_to_string(&mut buffer)?;
+
+        let net: Network = from_str(&buffer)?;
+        Ok(net)
+    }
+    ///Saves a network to `path` in a compact binary format, a fraction of the size of the JSON
+    ///written by save and holding exactly the same weights
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(32, Activations::SIGMOID, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///new_net.fit_batched(&inputs, &outputs, 20, 1).unwrap();
+    ///
+    ///let json_path = std::env::temp_dir().join("triton_save_bin_example.json");
+    ///let bin_path = std::env::temp_dir().join("triton_save_bin_example.bin");
+    ///new_net.save(json_path.to_str().unwrap()).unwrap();
+    ///new_net.save_bin(bin_path.to_str().unwrap()).unwrap();
+    ///
+    ///let mut from_json = Network::load(json_path.to_str().unwrap()).unwrap();
+    ///let mut from_bin = Network::load_bin(bin_path.to_str().unwrap()).unwrap();
+    ///for input in inputs {
+    ///    assert_eq!(from_json.predict(input.clone()).unwrap(), from_bin.predict(input).unwrap());
+    ///}
+    ///let json_size = std::fs::metadata(&json_path).unwrap().len();
+    ///let bin_size = std::fs::metadata(&bin_path).unwrap().len();
+    ///assert!(bin_size < json_size);
+    ///```
+    pub fn save_bin(&self, path: &str) -> Result<(), NetworkError> {
+        let mut file = File::create(path)?;
+        file.write_all(&binary::encode(&to_value(self)?))?;
+        Ok(())
+    }
+    ///Loads a network previously written with save_bin
+    pub fn load_bin(path: &str) -> Result<Network, NetworkError> {
+        let mut buffer = vec![];
+        let mut file = File::open(path)?;
+
+        file.read_to_end(&mut buffer)?;
+
+        let net: Network = from_value(binary::decode(&buffer)?)?;
+        Ok(net)
+    }
+
+    ///Exports the network to `path` as an ONNX model for inference runtimes, taking a float
+    ///tensor named "input" of shape (batch, input size). Dense layers become Gemm nodes followed by
+    ///their activation, Dropout is left out and SOFTMAXCE becomes a Softmax node
+    ///
+    ///Returns ExportError::UnsupportedLayer for any other layer and
+    ///ExportError::UnsupportedActivation for GELU, SWISH, MISH and custom activations, which ONNX has no operator for
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes, error::ExportError};
+    ///
+    ///let mut new_net = Network::new(2);
+    ///new_net.add_layer(LayerTypes::DENSE(3, Activations::RELU, 0.1));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///let path = std::env::temp_dir().join("triton_export_onnx_example.onnx");
+    ///new_net.export_onnx(path.to_str().unwrap()).unwrap();
+    ///let bytes = std::fs::read(&path).unwrap();
+    ///assert!(bytes.windows(4).any(|window| window == b"Gemm"));
+    ///
+    ///let mut recurrent = Network::new(2);
+    ///recurrent.add_layer(LayerTypes::RNN(3, Activations::TANH, 0.1));
+    ///recurrent.compile();
+    ///let err = recurrent.export_onnx(path.to_str().unwrap()).unwrap_err();
+    ///assert_eq!(err, ExportError::UnsupportedLayer { index: 0, layer: String::from("Rnn") });
+    ///```
+    pub fn export_onnx(&self, path: &str) -> Result<(), ExportError> {
+        let model = onnx::encode(self)?;
+        let mut file = File::create(path)?;
+        file.write_all(&model)?;
+        Ok(())
+    }
+    ///Post-training quantization for smaller deployments, converts every Dense layer's weights to
+    ///int8 with a scale factor per layer. The QuantizedNetwork's predict quantizes its inputs on
+    ///the fly and multiplies in integers, so its outputs only approximate the Network's
+    ///
+    ///Dropout layers are left out, as they do nothing during inference, and a SOFTMAXCE layer is
+    ///folded into a linear Dense layer before it. Returns ExportError::UnsupportedLayer for any
+    ///other layer
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![0.0]];
+    ///
+    ///let mut new_net = Network::new_seeded(2, 3);
+    ///new_net.add_layer(LayerTypes::DENSE(8, Activations::TANH, 0.05));
+    ///new_net.add_layer(LayerTypes::DROPOUT(0.0));
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.05));
+    ///new_net.compile();
+    ///new_net.fit_seeded(&inputs, &outputs, 500, 3).unwrap();
+    ///
+    ///let quantized = new_net.quantize().unwrap();
+    ///assert_eq!(quantized.layers.len(), 2);
+    ///for input in inputs.iter() {
+    ///    let expected = new_net.predict(input.clone()).unwrap()[0];
+    ///    let res = quantized.predict(input.clone()).unwrap()[0];
+    ///    assert!((res - expected).abs() < 0.02, "{} {}", res, expected);
+    ///}
+    ///assert!(quantized.predict(vec![1.0]).is_err());
+    ///```
+    pub fn quantize(&self) -> Result<QuantizedNetwork, ExportError> {
+        QuantizedNetwork::from_network(self)
+    }
+    ///Trains a neural network with fit, `steps_per` epochs at a time, until its loss is at or below
+    ///`desired_loss`. Returns how many epochs it trained for
+    ///
+    ///A `steps_per` of 0 can never lower the loss, so it returns straight away without training
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::{network::Network, activations::Activations, layer::layers::LayerTypes};
+    ///
+    ///let inputs: Vec<Vec<f32>> = vec![vec![0.0,0.0],vec![1.0,0.0],vec![0.0,1.0], vec![1.0,1.0]];
+    ///let outputs: Vec<Vec<f32>> = vec![vec![0.0],vec![1.0],vec![1.0], vec![1.0]];
+    ///
+    ///let mut new_net = Network::new_seeded(2, 1);
+    ///new_net.add_layer(LayerTypes::DENSE(1, Activations::SIGMOID, 0.1));
+    ///new_net.compile();
+    ///
+    ///assert_eq!(new_net.fit_to_loss(&inputs, &outputs, 0.1, 0).unwrap(), 0);
+    ///let epochs = new_net.fit_to_loss(&inputs, &outputs, 0.1, 10).unwrap();
+    ///assert!(epochs > 0 && epochs % 10 == 0);
+    ///assert!(new_net.get_loss_history()[9] <= 0.1);
+    ///```
+    pub fn fit_to_loss<Param: Input>(&mut self, train_in: &[Param], train_out: &[Param], desired_loss: f32, steps_per: usize) -> Result<usize, NetworkError>{
+        let mut epochs_total = 0;
+        if steps_per == 0 {
+            return Ok(epochs_total);
+        }
+        while self.loss > desired_loss{
+            self.fit(train_in, train_out, steps_per)?;
+            epochs_total += steps_per;
+            
+        }
+        self.log_trained();
+        Ok(epochs_total)
+    }
+}
+
+///Every training input needs a target, returns NetworkError::SampleCountMismatch if there are a
+///different number of each
+pub(crate) fn check_sample_count(inputs: usize, targets: usize) -> Result<(), NetworkError> {
+    if inputs != targets {
+        return Err(NetworkError::SampleCountMismatch { inputs, targets });
+    }
+    Ok(())
+}
+
+fn l2_norm(values: &[f32]) -> f32 {
+    values.iter().map(|value| value * value).sum::<f32>().sqrt()
+}
+
+///The class `values` stand for, the index of the largest value or 0 and 1 for a single value
+///thresholded at 0.5
+fn class_of(values: &[f32]) -> usize {
+    if values.len() == 1 {
+        (values[0] >= 0.5) as usize
+    } else {
+        argmax(values)
     }
 }
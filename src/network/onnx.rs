@@ -0,0 +1,198 @@
+use super::{network::Network, activations::Activations, error::ExportError};
+
+//A minimal ONNX ModelProto written straight in the protobuf wire format, only the handful of
+//fields an inference graph of Gemm and activation nodes needs
+const IR_VERSION: u64 = 7;
+const OPSET_VERSION: u64 = 13;
+
+//Protobuf wire types
+const VARINT: u64 = 0;
+const LENGTH_DELIMITED: u64 = 2;
+const FIXED32: u64 = 5;
+
+//TensorProto.DataType and AttributeProto.AttributeType values
+const TENSOR_FLOAT: u64 = 1;
+const ATTRIBUTE_FLOAT: u64 = 1;
+const ATTRIBUTE_INT: u64 = 2;
+
+const INPUT_NAME: &str = "input";
+
+enum Attribute {
+    Float(f32),
+    Int(i64),
+}
+
+///Encodes every layer of `net` into an ONNX model, Dense layers become a Gemm node followed by
+///their activation and Dropout layers (which do nothing during inference) are left out
+pub(crate) fn encode(net: &Network) -> Result<Vec<u8>, ExportError> {
+    if net.layers.is_empty() {
+        return Err(ExportError::EmptyNetwork);
+    }
+
+    let mut nodes: Vec<Vec<u8>> = vec![];
+    let mut initializers: Vec<Vec<u8>> = vec![];
+    let mut current = String::from(INPUT_NAME);
+    let mut width = net.layer_sizes[0];
+
+    for (index, layer) in net.layers.iter().enumerate() {
+        match layer.typetag_name() {
+            "Dense" => {
+                let (rows, columns, _) = layer.shape();
+                let parameters = layer.parameters();
+                let (weights, biases) = parameters.split_at(rows * columns);
+
+                let weight_name = format!("dense{}_weight", index);
+                initializers.push(tensor(&weight_name, &[rows, columns], weights));
+                let mut inputs = vec![current.clone(), weight_name];
+                if !biases.is_empty() {
+                    let bias_name = format!("dense{}_bias", index);
+                    initializers.push(tensor(&bias_name, &[rows], biases));
+                    inputs.push(bias_name);
+                }
+
+                //Every row of the weights is one output, so they're multiplied transposed
+                let gemm_name = format!("dense{}_gemm", index);
+                nodes.push(node(&gemm_name, "Gemm", &inputs, &[("transB", Attribute::Int(1))]));
+                current = gemm_name;
+                width = rows;
+
+                if let Some(activation) = layer.get_activation() {
+                    let (op_type, attributes) = activation_node(activation).ok_or_else(|| ExportError::UnsupportedActivation { index, activation: format!("{:?}", activation) })?;
+                    let activation_name = format!("dense{}_out", index);
+                    nodes.push(node(&activation_name, op_type, &[current], &attributes));
+                    current = activation_name;
+                }
+            },
+            "Dropout" => {},
+            "SoftmaxCE" => {
+                let softmax_name = format!("softmax{}_out", index);
+                nodes.push(node(&softmax_name, "Softmax", &[current], &[("axis", Attribute::Int(1))]));
+                current = softmax_name;
+            },
+            name => return Err(ExportError::UnsupportedLayer { index, layer: name.to_string() }),
+        }
+    }
+
+    let mut graph = vec![];
+    for node in nodes.iter() {
+        write_bytes(1, node, &mut graph);
+    }
+    write_bytes(2, b"triton", &mut graph);
+    for initializer in initializers.iter() {
+        write_bytes(5, initializer, &mut graph);
+    }
+    write_bytes(11, &value_info(INPUT_NAME, net.layer_sizes[0]), &mut graph);
+    write_bytes(12, &value_info(&current, width), &mut graph);
+
+    let mut opset = vec![];
+    write_bytes(1, b"", &mut opset);
+    write_varint_field(2, OPSET_VERSION, &mut opset);
+
+    let mut model = vec![];
+    write_varint_field(1, IR_VERSION, &mut model);
+    write_bytes(2, b"triton_grow", &mut model);
+    write_bytes(3, env!("CARGO_PKG_VERSION").as_bytes(), &mut model);
+    write_bytes(7, &graph, &mut model);
+    write_bytes(8, &opset, &mut model);
+    Ok(model)
+}
+
+//The ONNX operator (and attributes) computing an activation, None for the ones ONNX has no
+//operator for in this opset
+fn activation_node(activation: Activations) -> Option<(&'static str, Vec<(&'static str, Attribute)>)> {
+    match activation {
+        Activations::SIGMOID => Some(("Sigmoid", vec![])),
+        Activations::TANH => Some(("Tanh", vec![])),
+        Activations::RELU => Some(("Relu", vec![])),
+        Activations::LEAKYRELU(slope) => Some(("LeakyRelu", vec![("alpha", Attribute::Float(slope))])),
+        Activations::ELU(alpha) => Some(("Elu", vec![("alpha", Attribute::Float(alpha))])),
+        Activations::SOFTMAX => Some(("Softmax", vec![("axis", Attribute::Int(1))])),
+        Activations::GELU | Activations::SWISH | Activations::MISH | Activations::CUSTOM(_) => None,
+    }
+}
+
+fn node(name: &str, op_type: &str, inputs: &[String], attributes: &[(&str, Attribute)]) -> Vec<u8> {
+    let mut res = vec![];
+    for input in inputs {
+        write_bytes(1, input.as_bytes(), &mut res);
+    }
+    write_bytes(2, name.as_bytes(), &mut res);
+    write_bytes(3, name.as_bytes(), &mut res);
+    write_bytes(4, op_type.as_bytes(), &mut res);
+    for (attribute_name, value) in attributes {
+        let mut attribute = vec![];
+        write_bytes(1, attribute_name.as_bytes(), &mut attribute);
+        match value {
+            Attribute::Float(float) => {
+                write_key(2, FIXED32, &mut attribute);
+                attribute.extend_from_slice(&float.to_le_bytes());
+                write_varint_field(20, ATTRIBUTE_FLOAT, &mut attribute);
+            },
+            Attribute::Int(int) => {
+                write_varint_field(3, *int as u64, &mut attribute);
+                write_varint_field(20, ATTRIBUTE_INT, &mut attribute);
+            },
+        }
+        write_bytes(5, &attribute, &mut res);
+    }
+    res
+}
+
+fn tensor(name: &str, dims: &[usize], values: &[f32]) -> Vec<u8> {
+    let mut res = vec![];
+    for dim in dims {
+        write_varint_field(1, *dim as u64, &mut res);
+    }
+    write_varint_field(2, TENSOR_FLOAT, &mut res);
+    write_bytes(8, name.as_bytes(), &mut res);
+    let raw: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+    write_bytes(9, &raw, &mut res);
+    res
+}
+
+//A float tensor of shape (N, width), where the batch size N is left symbolic
+fn value_info(name: &str, width: usize) -> Vec<u8> {
+    let mut batch = vec![];
+    write_bytes(2, b"N", &mut batch);
+    let mut features = vec![];
+    write_varint_field(1, width as u64, &mut features);
+
+    let mut shape = vec![];
+    write_bytes(1, &batch, &mut shape);
+    write_bytes(1, &features, &mut shape);
+
+    let mut tensor_type = vec![];
+    write_varint_field(1, TENSOR_FLOAT, &mut tensor_type);
+    write_bytes(2, &shape, &mut tensor_type);
+
+    let mut type_proto = vec![];
+    write_bytes(1, &tensor_type, &mut type_proto);
+
+    let mut res = vec![];
+    write_bytes(1, name.as_bytes(), &mut res);
+    write_bytes(2, &type_proto, &mut res);
+    res
+}
+
+fn write_key(field: u64, wire_type: u64, out: &mut Vec<u8>) {
+    write_varint((field << 3) | wire_type, out);
+}
+
+fn write_varint_field(field: u64, value: u64, out: &mut Vec<u8>) {
+    write_key(field, VARINT, out);
+    write_varint(value, out);
+}
+
+fn write_bytes(field: u64, bytes: &[u8], out: &mut Vec<u8>) {
+    write_key(field, LENGTH_DELIMITED, out);
+    write_varint(bytes.len() as u64, out);
+    out.extend_from_slice(bytes);
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
@@ -0,0 +1,44 @@
+use serde::{Serialize, Deserialize};
+
+///The adaptive update rule Dense layers apply their gradients with, see Network::set_optimizer.
+///A momentum set with Network::set_momentum takes precedence and switches layers to SGD
+///
+///# Example
+///
+///AdamW shrinks the weights directly, while weight decay under Adam is an L2 term of the gradient
+///that gets normalized by the moment estimates like the rest of it
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, dense::Dense}, activations::Activations, optimizer::Optimizer, input::Input, matrix::Matrix};
+///
+///let mut adamw = Dense::new(2, 3, Activations::TANH, 0.1);
+///let mut l2 = adamw.clone();
+///adamw.set_optimizer(Optimizer::AdamW { weight_decay: 0.5 });
+///l2.set_weight_decay(0.5);
+///let before = adamw.weights.to_param();
+///
+/////Without any error left the only thing moving the weights is the decay
+///for layer in [&mut adamw, &mut l2] {
+///    layer.forward(&vec![1.0, -1.0]);
+///    let errors: Box<dyn Input> = Box::new(vec![vec![0.0]; 3]);
+///    layer.backward(layer.update_gradient(), errors, Box::new(Matrix::from(vec![vec![1.0, -1.0]]).transpose()));
+///    layer.apply_gradients(1.0);
+///}
+///
+/////Decoupled, every weight shrinks in proportion to its size by lr * weight_decay
+///for (after, before) in adamw.weights.to_param().iter().zip(before.iter()) {
+///    assert!((after - before * (1.0 - 0.1 * 0.5)).abs() < 1e-6);
+///}
+/////Through Adam, every weight takes the same sized step no matter how large it is
+///for (after, before) in l2.weights.to_param().iter().zip(before.iter()) {
+///    assert!(((before - after).abs() - 0.1).abs() < 1e-3);
+///}
+///```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Optimizer{
+    #[default]
+    Adam,
+    ///Adam with decoupled weight decay, after every update the weights are shrunk by
+    ///`learning_rate * weight_decay` times their value. Biases aren't decayed
+    AdamW { weight_decay: f32 },
+}
@@ -0,0 +1,128 @@
+use serde::{Serialize, Deserialize};
+
+///How a Scaler rescales every feature
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Scaling{
+    ///Shifts every feature to a mean of 0 and a standard deviation of 1
+    #[default]
+    Standard,
+    ///Rescales every feature to [0, 1] by the minimum and maximum it was fit on
+    MinMax,
+}
+
+///Rescales every feature of a dataset by statistics learned from the training data, so the
+///exact same transform can be applied at inference. Serializes alongside the network it was
+///trained for
+///
+///Features that never change in the data the scaler was fit on are only shifted
+///
+///# Example
+///
+///```
+///use triton_grow::network::preprocess::{Scaler, Scaling};
+///
+///let data = vec![vec![1.0, 100.0], vec![2.0, 300.0], vec![3.0, 500.0]];
+///
+///let mut scaler = Scaler::new(Scaling::Standard);
+///let scaled = scaler.fit_transform(&data);
+///for feature in 0..2 {
+///    let mean = scaled.iter().map(|sample| sample[feature]).sum::<f32>() / 3.0;
+///    assert!(mean.abs() < 1e-6);
+///}
+///
+///let saved = serde_json::to_string(&scaler).unwrap();
+///let loaded: Scaler = serde_json::from_str(&saved).unwrap();
+///let restored = loaded.inverse_transform(&loaded.transform(&data));
+///for (sample, original) in restored.iter().zip(data.iter()) {
+///    for (value, expected) in sample.iter().zip(original.iter()) {
+///        assert!((value - expected).abs() < 1e-3);
+///    }
+///}
+///
+///let mut scaler = Scaler::new(Scaling::MinMax);
+///scaler.fit(&data);
+///assert_eq!(scaler.transform(&[vec![2.0, 500.0]]), vec![vec![0.5, 1.0]]);
+///```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Scaler{
+    pub scaling: Scaling,
+    //Every feature is transformed into (x - shift) / scale
+    shifts: Vec<f32>,
+    scales: Vec<f32>,
+}
+
+impl Scaler{
+    ///Creates a Scaler that still has to be fit before it transforms anything
+    pub fn new(scaling: Scaling) -> Scaler {
+        Scaler { scaling, shifts: vec![], scales: vec![] }
+    }
+    ///Learns the per-feature statistics of `data`, where every Vec is one sample
+    pub fn fit(&mut self, data: &[Vec<f32>]) {
+        let features = data.first().map_or(0, |sample| sample.len());
+        let samples = data.len() as f32;
+        self.shifts = Vec::with_capacity(features);
+        self.scales = Vec::with_capacity(features);
+
+        for feature in 0..features {
+            let (shift, spread) = match self.scaling {
+                Scaling::Standard => {
+                    let mean = data.iter().map(|sample| sample[feature]).sum::<f32>() / samples;
+                    let variance = data.iter().map(|sample| (sample[feature] - mean).powi(2)).sum::<f32>() / samples;
+                    (mean, variance.sqrt())
+                },
+                Scaling::MinMax => {
+                    let min = data.iter().map(|sample| sample[feature]).fold(f32::INFINITY, f32::min);
+                    let max = data.iter().map(|sample| sample[feature]).fold(f32::NEG_INFINITY, f32::max);
+                    (min, max - min)
+                }
+            };
+            self.shifts.push(shift);
+            self.scales.push(if spread > 0.0 { spread } else { 1.0 });
+        }
+    }
+    ///Rescales every sample of `data` with the statistics learned by fit
+    ///
+    ///Panics if the scaler hasn't been fit yet or a sample doesn't have as many features as the
+    ///data it was fit on
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::preprocess::{Scaler, Scaling};
+    ///
+    ///let mut scaler = Scaler::new(Scaling::MinMax);
+    ///assert!(std::panic::catch_unwind(|| scaler.transform(&[vec![1.0, 2.0]])).is_err());
+    ///
+    ///scaler.fit(&[vec![0.0, 0.0], vec![2.0, 4.0]]);
+    ///assert_eq!(scaler.transform(&[vec![1.0, 2.0]]), vec![vec![0.5, 0.5]]);
+    ///assert!(std::panic::catch_unwind(|| scaler.transform(&[vec![1.0]])).is_err());
+    ///assert!(std::panic::catch_unwind(|| scaler.inverse_transform(&[vec![1.0, 2.0, 3.0]])).is_err());
+    ///```
+    pub fn transform(&self, data: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        data.iter().map(|sample| {
+            self.check_width(sample);
+            sample.iter().zip(self.shifts.iter().zip(self.scales.iter())).map(|(value, (shift, scale))| (value - shift) / scale).collect()
+        }).collect()
+    }
+    ///Undoes transform, mapping scaled samples (or a network's scaled outputs) back to the
+    ///original units. Panics just like transform
+    pub fn inverse_transform(&self, data: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        data.iter().map(|sample| {
+            self.check_width(sample);
+            sample.iter().zip(self.shifts.iter().zip(self.scales.iter())).map(|(value, (shift, scale))| value * scale + shift).collect()
+        }).collect()
+    }
+    fn check_width(&self, sample: &[f32]) {
+        if self.shifts.is_empty() {
+            panic!("Error attempting to transform with a Scaler that hasn't been fit yet");
+        }
+        if sample.len() != self.shifts.len() {
+            panic!("Error attempting to transform a sample of {} features with a Scaler fit on {}", sample.len(), self.shifts.len());
+        }
+    }
+    ///Fits the scaler to `data` and returns it transformed
+    pub fn fit_transform(&mut self, data: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        self.fit(data);
+        self.transform(data)
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Serialize, Deserialize};
+
+use super::{network::Network, matrix::Matrix, activations::Activations, error::{NetworkError, ExportError}};
+
+//Symmetric int8 quantization maps [-max, max] onto [-127, 127], leaving -128 unused so 0 stays
+//exactly 0
+const QUANTIZED_MAX: f32 = 127.0;
+
+///A Dense layer whose weights are stored as int8 with a single scale factor, see
+///Network::quantize
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuantizedDense {
+    pub rows: usize,
+    pub columns: usize,
+    ///The weights in row-major order, every weight is `weight as f32 * scale`
+    pub weights: Vec<i8>,
+    pub scale: f32,
+    ///Biases are kept as f32, there are few enough of them not to matter
+    pub biases: Vec<f32>,
+    pub activation: Activations,
+}
+
+///A network of int8 Dense layers for inference, a quarter of the size of the Network it was
+///quantized from. Built with Network::quantize
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuantizedNetwork {
+    pub input_size: usize,
+    pub layers: Vec<QuantizedDense>,
+}
+
+impl QuantizedDense {
+    fn new(rows: usize, columns: usize, weights: &[f32], biases: &[f32], activation: Activations) -> QuantizedDense {
+        let (weights, scale) = quantize(weights);
+        QuantizedDense { rows, columns, weights, scale, biases: biases.to_vec(), activation }
+    }
+    //Quantizes the input on the fly, multiplies in i32 and scales the sums back to f32
+    fn forward(&self, input: &[f32]) -> Vec<f32> {
+        let (input, input_scale) = quantize(input);
+        let scale = self.scale * input_scale;
+        let outputs: Vec<f32> = self.weights.chunks(self.columns.max(1)).enumerate().map(|(row, weights)| {
+            let sum: i32 = weights.iter().zip(input.iter()).map(|(&weight, &value)| weight as i32 * value as i32).sum();
+            sum as f32 * scale + self.biases.get(row).copied().unwrap_or(0.0)
+        }).collect();
+        self.activation.apply(Matrix::from(vec![outputs]).transpose()).flatten()
+    }
+}
+
+impl QuantizedNetwork {
+    pub(crate) fn from_network(net: &Network) -> Result<QuantizedNetwork, ExportError> {
+        if net.layers.is_empty() {
+            return Err(ExportError::EmptyNetwork);
+        }
+        let mut layers: Vec<QuantizedDense> = vec![];
+        for (index, layer) in net.layers.iter().enumerate() {
+            match layer.typetag_name() {
+                "Dense" => {
+                    let (rows, columns, _) = layer.shape();
+                    let parameters = layer.parameters();
+                    let (weights, biases) = parameters.split_at(rows * columns);
+                    let activation = layer.get_activation().ok_or_else(|| ExportError::UnsupportedLayer { index, layer: String::from("Dense") })?;
+                    layers.push(QuantizedDense::new(rows, columns, weights, biases, activation));
+                },
+                "Dropout" => {},
+                //Softmax over the logits of a linear (LEAKYRELU(1.0)) Dense layer is the same as that
+                //layer using SOFTMAX
+                "SoftmaxCE" => match layers.last_mut() {
+                    Some(last) if matches!(last.activation, Activations::LEAKYRELU(slope) if slope == 1.0) => last.activation = Activations::SOFTMAX,
+                    _ => return Err(ExportError::UnsupportedLayer { index, layer: String::from("SoftmaxCE") }),
+                },
+                name => return Err(ExportError::UnsupportedLayer { index, layer: name.to_string() }),
+            }
+        }
+        Ok(QuantizedNetwork { input_size: net.layer_sizes[0], layers })
+    }
+    ///Runs a single input through every quantized layer
+    ///
+    ///Returns NetworkError::InputSizeMismatch if the input does not fit the first layer
+    pub fn predict(&self, input: Vec<f32>) -> Result<Vec<f32>, NetworkError> {
+        if input.len() != self.input_size {
+            return Err(NetworkError::InputSizeMismatch { expected: self.input_size, got: input.len() });
+        }
+        Ok(self.layers.iter().fold(input, |data, layer| layer.forward(&data)))
+    }
+}
+
+//Symmetric quantization of `values` by their largest magnitude, returns the quantized values and
+//the scale that turns them back into f32
+fn quantize(values: &[f32]) -> (Vec<i8>, f32) {
+    let max = values.iter().fold(0.0f32, |max, value| max.max(value.abs()));
+    let scale = if max > 0.0 { max / QUANTIZED_MAX } else { 1.0 };
+    (values.iter().map(|value| (value / scale).round().clamp(-QUANTIZED_MAX, QUANTIZED_MAX) as i8).collect(), scale)
+}
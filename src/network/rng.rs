@@ -0,0 +1,35 @@
+use std::cell::RefCell;
+
+use rand::{rngs::StdRng, thread_rng, RngCore, SeedableRng};
+
+thread_local! {
+    static SEEDED: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+///Runs `f` with every random weight (Matrix::new_random, He and Xavier initialization) drawn from
+///an RNG seeded with `seed` instead of the thread's RNG, so layers constructed inside come out
+///identical every time
+///
+///# Example
+///
+///```
+///use triton_grow::network::{layer::{layers::Layer, dense::Dense}, activations::Activations, rng};
+///
+///let first = rng::with_seed(7, || Dense::new(3, 2, Activations::SIGMOID, 0.1));
+///let second = rng::with_seed(7, || Dense::new(3, 2, Activations::SIGMOID, 0.1));
+///assert_eq!(first.parameters(), second.parameters());
+///```
+pub fn with_seed<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    let previous = SEEDED.with(|seeded| seeded.replace(Some(StdRng::seed_from_u64(seed))));
+    let res = f();
+    SEEDED.with(|seeded| *seeded.borrow_mut() = previous);
+    res
+}
+
+//Hands `f` the seeded RNG when inside with_seed, otherwise the thread's RNG
+pub(crate) fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    SEEDED.with(|seeded| match seeded.borrow_mut().as_mut() {
+        Some(rng) => f(rng),
+        None => f(&mut thread_rng()),
+    })
+}
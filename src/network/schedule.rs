@@ -0,0 +1,65 @@
+use serde::{Serialize, Deserialize};
+
+///How a network's learning rates change over the course of training, each layer's learning rate
+///is scaled from the one it was declared with at the start of every epoch
+///
+///# Example
+///
+///```
+///use triton_grow::network::schedule::Schedule;
+///
+///let schedule = Schedule::StepDecay { step: 2, gamma: 0.5 };
+///assert_eq!(schedule.learning_rate(0.1, 0), 0.1);
+///assert_eq!(schedule.learning_rate(0.1, 1), 0.1);
+///assert_eq!(schedule.learning_rate(0.1, 2), 0.05);
+///assert_eq!(schedule.learning_rate(0.1, 4), 0.025);
+///```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Schedule{
+    ///Keeps every layer's declared learning rate
+    #[default]
+    Constant,
+    ///Multiplies the learning rate by `gamma` every `step` epochs
+    StepDecay { step: usize, gamma: f32 },
+    ///Multiplies the learning rate by `gamma` every epoch
+    ExponentialDecay { gamma: f32 },
+    ///Cosine annealing with warm restarts (SGDR), follows half a cosine from the declared
+    ///learning rate down towards `eta_min` over `t_max` epochs, then restarts from the top
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use triton_grow::network::schedule::Schedule;
+    ///
+    ///let schedule = Schedule::CosineAnnealing { t_max: 4, eta_min: 0.0 };
+    ///let rates: Vec<f32> = (0..9).map(|epoch| schedule.learning_rate(1.0, epoch)).collect();
+    ///
+    ///let expected = [1.0, 0.853_553_4, 0.5, 0.146_446_6];
+    ///for (epoch, rate) in rates.iter().enumerate() {
+    ///    assert!((rate - expected[epoch % 4]).abs() < 1e-6, "epoch {}: {}", epoch, rate);
+    ///}
+    ///assert!(rates.windows(2).take(3).all(|pair| pair[1] < pair[0]));
+    ///assert_eq!(rates[4], 1.0);
+    ///
+    ///let floored = Schedule::CosineAnnealing { t_max: 2, eta_min: 0.01 };
+    ///assert!((floored.learning_rate(0.1, 1) - 0.055).abs() < 1e-6);
+    ///```
+    CosineAnnealing { t_max: usize, eta_min: f32 },
+}
+
+impl Schedule{
+    ///The learning rate to train `epoch` (counting from 0) with, given the rate a layer was
+    ///declared with
+    pub fn learning_rate(&self, base: f32, epoch: usize) -> f32 {
+        match self {
+            Schedule::Constant => base,
+            Schedule::StepDecay { step, gamma } => base * gamma.powi((epoch / (*step).max(1)) as i32),
+            Schedule::ExponentialDecay { gamma } => base * gamma.powi(epoch as i32),
+            Schedule::CosineAnnealing { t_max, eta_min } => {
+                let t_max = (*t_max).max(1);
+                let progress = (epoch % t_max) as f32 / t_max as f32;
+                eta_min + (base - eta_min) * (1.0 + (std::f32::consts::PI * progress).cos()) / 2.0
+            },
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::{activations::Activations, builder::NetworkBuilder, data::Dataset, error::NetworkError, loss::Loss, modes::Task, network::{Network, EvalResult}};
+
+///Every hyperparameter grid_search tries, it builds one network per combination of a learning
+///rate, a hidden layer size and a hidden activation
+#[derive(Clone, Debug)]
+pub struct ConfigSpace {
+    pub learning_rates: Vec<f32>,
+    pub hidden_sizes: Vec<usize>,
+    pub activations: Vec<Activations>,
+    ///How many values the output layer has
+    pub output_size: usize,
+    ///The activation of the output layer, the same for every combination
+    pub output_activation: Activations,
+    pub loss: Loss,
+    pub task: Task,
+    ///How many epochs every combination trains for
+    pub epochs: usize,
+}
+
+///A single combination of hyperparameters out of a ConfigSpace
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub learning_rate: f32,
+    pub hidden_size: usize,
+    pub activation: Activations,
+}
+
+///The combination grid_search found to score best on the validation set
+#[derive(Clone)]
+pub struct BestConfig {
+    pub config: Config,
+    ///How the trained network did on the validation set
+    pub result: EvalResult,
+    ///The network trained with the config, ready to use
+    pub network: Network,
+}
+
+///Builds a network of one hidden layer for every combination in `space`, trains it on `train`
+///for `space.epochs` epochs and evaluates it on `val`, returning the combination with the lowest
+///validation loss. Combinations are tried in order and earlier ones win ties
+///
+///Returns NetworkError::EmptyNetwork if `space` has no combinations to try, and any error
+///building, training or evaluating a network raises
+///
+///# Example
+///
+///```
+///use triton_grow::network::{tune::{grid_search, ConfigSpace}, activations::Activations, loss::Loss, modes::Task};
+///
+///let inputs: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32 / 20.0]).collect();
+///let outputs: Vec<Vec<f32>> = inputs.iter().map(|input| vec![(input[0] > 0.5) as u8 as f32]).collect();
+///let train = (inputs.clone(), outputs.clone());
+///let val = (inputs, outputs);
+///
+///let space = ConfigSpace {
+///    learning_rates: vec![0.000001, 0.05],
+///    hidden_sizes: vec![2, 4],
+///    activations: vec![Activations::TANH, Activations::SIGMOID],
+///    output_size: 1,
+///    output_activation: Activations::SIGMOID,
+///    loss: Loss::CROSSENTROPY,
+///    task: Task::CLASSIFICATION,
+///    epochs: 100,
+///};
+///
+///let mut best = grid_search(&space, &train, &val).unwrap();
+///assert_eq!(best.config.learning_rate, 0.05);
+///assert!(best.result.accuracy > 0.8);
+///assert_eq!(best.network.evaluate(&val.0, &val.1).unwrap(), best.result);
+///
+///let empty = ConfigSpace { hidden_sizes: vec![], ..space };
+///assert!(grid_search(&empty, &train, &val).is_err());
+///```
+pub fn grid_search(space: &ConfigSpace, train: &Dataset, val: &Dataset) -> Result<BestConfig, NetworkError> {
+    let input_size = train.0.first().map_or(0, |input| input.len());
+    let mut best: Option<BestConfig> = None;
+
+    for &learning_rate in space.learning_rates.iter() {
+        for &hidden_size in space.hidden_sizes.iter() {
+            for &activation in space.activations.iter() {
+                let config = Config { learning_rate, hidden_size, activation };
+                let mut network = NetworkBuilder::new()
+                    .input(input_size)
+                    .dense(hidden_size, activation, learning_rate)
+                    .dense(space.output_size, space.output_activation, learning_rate)
+                    .build()?;
+                network.set_loss(space.loss);
+                network.set_task(space.task);
+
+                network.fit(&train.0, &train.1, space.epochs)?;
+                let result = network.evaluate(&val.0, &val.1)?;
+                if best.as_ref().is_none_or(|best| result.loss < best.result.loss) {
+                    best = Some(BestConfig { config, result, network });
+                }
+            }
+        }
+    }
+    best.ok_or(NetworkError::EmptyNetwork)
+}